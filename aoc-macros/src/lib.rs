@@ -0,0 +1,125 @@
+//! `#[aoc(dayN, partM[, name])]`, `#[aoc_generator(dayN[, partM[, name]])]`,
+//! `#[aoc_stream(dayN, partM[, name])]` and `#[aoc_visual(dayN)]`.
+//!
+//! The attributes mark solvers and generators for the main crate's `build.rs`, which finds
+//! them by scanning the sources. They leave the function as it is and only check their
+//! arguments, so a typo is a compile error instead of a solver that silently goes missing.
+
+use proc_macro::{TokenStream, TokenTree};
+
+/// Marks a solver. Takes `&str` or a reference to its generator's output, and returns
+/// anything `Display`, optionally wrapped in `Result` or `Option`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    checked(validate(&arguments(args), true), item)
+}
+
+/// Marks a generator, whose output is passed to the solvers of the same day (and part and
+/// name, if given).
+#[proc_macro_attribute]
+pub fn aoc_generator(args: TokenStream, item: TokenStream) -> TokenStream {
+    checked(validate(&arguments(args), false), item)
+}
+
+/// Marks a single-pass version of the solver with the same day, part and name. Takes
+/// `&mut dyn BufRead` and returns like a solver.
+#[proc_macro_attribute]
+pub fn aoc_stream(args: TokenStream, item: TokenStream) -> TokenStream {
+    checked(validate(&arguments(args), true), item)
+}
+
+/// Marks a visualisation of a day. Takes the input as `&str` and returns a
+/// `render::Picture`, optionally wrapped in `Result` or `Option`.
+#[proc_macro_attribute]
+pub fn aoc_visual(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = arguments(args);
+    let valid = match args.len() {
+        1 => validate(&args, false),
+        _ => Err("expected `#[aoc_visual(dayN)]`".to_string()),
+    };
+    checked(valid, item)
+}
+
+fn checked(valid: Result<(), String>, item: TokenStream) -> TokenStream {
+    match valid {
+        Ok(()) => item,
+        Err(msg) => {
+            let mut out: TokenStream = format!("compile_error!({msg:?});").parse().unwrap();
+            out.extend(item);
+            out
+        }
+    }
+}
+
+/// The comma-separated arguments, as written.
+fn arguments(args: TokenStream) -> Vec<String> {
+    let mut out = vec![String::new()];
+    for tt in args {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => out.push(String::new()),
+            tt => out.last_mut().unwrap().push_str(&tt.to_string()),
+        }
+    }
+    if out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+    out
+}
+
+fn validate(args: &[String], solver: bool) -> Result<(), String> {
+    let usage = if solver {
+        "expected `#[aoc(dayN, partM)]` or `#[aoc(dayN, partM, name)]`"
+    } else {
+        "expected `#[aoc_generator(dayN)]`, optionally with `partM` and a name"
+    };
+    let min = if solver { 2 } else { 1 };
+    if args.len() < min || args.len() > 3 {
+        return Err(usage.to_string());
+    }
+    let day = args[0].strip_prefix("day").and_then(|d| d.parse().ok());
+    if !matches!(day, Some(1..=25)) {
+        return Err(format!("bad day `{}`, {usage}", args[0]));
+    }
+    if let Some(part) = args.get(1) {
+        if part != "part1" && part != "part2" {
+            return Err(format!("bad part `{part}`, {usage}"));
+        }
+    }
+    if let Some(name) = args.get(2) {
+        let ident = name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !ident || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("bad name `{name}`, {usage}"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::validate;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(',').map(|a| a.trim().to_string()).collect()
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(validate(&args("day5, part1"), true), Ok(()));
+        assert_eq!(validate(&args("day25, part2, naive"), true), Ok(()));
+        assert_eq!(validate(&args("day5"), false), Ok(()));
+        assert_eq!(validate(&args("day5, part2"), false), Ok(()));
+    }
+
+    #[test]
+    fn invalid() {
+        let e = validate(&args("day5"), true).unwrap_err();
+        assert!(e.starts_with("expected `#[aoc(dayN, partM)]`"));
+        let e = validate(&args("day26, part1"), true).unwrap_err();
+        assert!(e.starts_with("bad day `day26`"));
+        let e = validate(&args("day5, part3"), true).unwrap_err();
+        assert!(e.starts_with("bad part `part3`"));
+        let e = validate(&args("day5, part1, 2x"), true).unwrap_err();
+        assert!(e.starts_with("bad name `2x`"));
+        assert!(validate(&args("day5, part1, a, b"), true).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Discovers every `#[aoc(...)]` solver, `#[aoc_generator(...)]`, `#[aoc_stream(...)]` and
+//! `#[aoc_visual(...)]` under `src/y<year>/` and writes the tables used by
+//! `runner::SOLVERS` and `runner::VISUALS`, plus one test per line of each year's answers
+//! file for `tests/answers.rs`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `(year, day, part, name)`, with the year from the module path and the rest as written
+/// in the attribute.
+type Key = (u32, u32, Option<u32>, Option<String>);
+
+/// `(day, part, name)` as written in the attribute.
+type Attr = (u32, Option<u32>, Option<String>);
+
+struct Func {
+    path: String,
+    ret: Ret,
+}
+
+#[derive(Clone, Copy)]
+enum Ret {
+    Plain,
+    Result,
+    Option,
+}
+
+impl Ret {
+    fn unwrap(&self) -> &'static str {
+        match self {
+            Ret::Plain => "",
+            Ret::Result => "?",
+            Ret::Option => ".ok_or_else(|| ::eyre::eyre!(\"no value\"))?",
+        }
+    }
+}
+
+/// Holds `<year>/answers.txt` for each year.
+const INPUT: &str = "input";
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed={INPUT}");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+
+    let mut generators: HashMap<Key, Func> = HashMap::new();
+    let mut streams: HashMap<Key, Func> = HashMap::new();
+    let mut visuals: Vec<(Key, Func)> = vec![];
+    let mut solvers: Vec<(Key, Func)> = vec![];
+    for file in rust_files(Path::new("src")) {
+        let Some(module) = module_path(&file) else {
+            continue;
+        };
+        let text = fs::read_to_string(&file).expect("readable source file");
+        let found = scan(&text, &module);
+        if found.is_empty() {
+            continue;
+        }
+        let year = module
+            .split("::")
+            .nth(1)
+            .and_then(|m| m.strip_prefix('y'))
+            .and_then(|y| y.parse().ok())
+            .unwrap_or_else(|| panic!("{module}: solvers belong in a `y<year>` module"));
+        for (attr, (day, part, name), func) in found {
+            let key = (year, day, part, name);
+            match attr {
+                "aoc_generator" => {
+                    generators.insert(key, func);
+                }
+                "aoc_stream" => {
+                    streams.insert(key, func);
+                }
+                "aoc_visual" => visuals.push((key, func)),
+                _ => solvers.push((key, func)),
+            }
+        }
+    }
+    solvers.sort_by(|a, b| a.0.cmp(&b.0));
+    visuals.sort_by(|a, b| a.0.cmp(&b.0));
+    for (key, stream) in &streams {
+        if !solvers.iter().any(|(k, _)| k == key) {
+            panic!("{}: no solver for {key:?}", stream.path);
+        }
+    }
+    for ((year, day, ..), visual) in &visuals {
+        if !solvers.iter().any(|((y, d, ..), _)| (y, d) == (year, day)) {
+            panic!("{}: no solver for {year} day {day}", visual.path);
+        }
+    }
+
+    let mut out = String::from("&[\n");
+    for ((year, day, part, name), solver) in &solvers {
+        let generator = [
+            (*year, *day, *part, name.clone()),
+            (*year, *day, *part, None),
+            (*year, *day, None, None),
+        ]
+        .into_iter()
+        .find_map(|k| generators.get(&k));
+        let generate = match generator {
+            Some(g) => format!("{}(input){}", g.path, g.ret.unwrap()),
+            None => "input".to_string(),
+        };
+        let stream = match streams.get(&(*year, *day, *part, name.clone())) {
+            Some(s) => format!(
+                "Some(|input| Ok(Answer::from({}(input){})))",
+                s.path,
+                s.ret.unwrap()
+            ),
+            None => "None".to_string(),
+        };
+        writeln!(
+            out,
+            "    Solver {{\n        year: {year},\n        day: {day},\n        part: {},\n        name: {:?},\n        \
+             prepare: |input| {{\n            \
+             Ok(Box::new(Prepared::new({generate}, |input| {{\n                \
+             Ok(Answer::from({}(::std::borrow::Borrow::borrow(input)){}))\n            \
+             }})))\n        }},\n        stream: {stream},\n    }},",
+            part.expect("solvers have a part"),
+            name.as_deref(),
+            solver.path,
+            solver.ret.unwrap(),
+        )
+        .unwrap();
+    }
+    out.push_str("]\n");
+
+    fs::write(out_dir.join("solvers.rs"), out).expect("writable OUT_DIR");
+
+    let mut out = String::from("&[\n");
+    for ((year, day, ..), visual) in &visuals {
+        let draw = match visual.ret {
+            Ret::Result => visual.path.clone(),
+            ret => format!("|input| Ok({}(input){})", visual.path, ret.unwrap()),
+        };
+        writeln!(
+            out,
+            "    Visual {{\n        year: {year},\n        day: {day},\n        draw: {draw},\n    }},",
+        )
+        .unwrap();
+    }
+    out.push_str("]\n");
+    fs::write(out_dir.join("visuals.rs"), out).expect("writable OUT_DIR");
+    fs::write(out_dir.join("answer_tests.rs"), answer_tests()).expect("writable OUT_DIR");
+}
+
+/// One `#[test]` per answer, named after its year, day, part and input file.
+fn answer_tests() -> String {
+    let mut years = fs::read_dir(INPUT)
+        .map(|dir| {
+            dir.filter_map(|e| e.ok()?.file_name().to_str()?.parse::<u32>().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    years.sort();
+    let mut out = String::new();
+    for year in years {
+        let path = Path::new(INPUT).join(year.to_string()).join("answers.txt");
+        let text = fs::read_to_string(path).unwrap_or_default();
+        for (i, l) in text.lines().enumerate() {
+            let fields = l.split_ascii_whitespace().collect::<Vec<_>>();
+            if fields.is_empty() || fields[0].starts_with('#') {
+                continue;
+            }
+            let mut name = format!("y{year}_{}", fields[..fields.len() - 1].join("_"));
+            name = name
+                .trim_end_matches(".txt")
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+            writeln!(
+                out,
+                "#[test]\nfn {name}() -> ::eyre::Result<()> {{\n    check_line({year}, {})\n}}\n",
+                i + 1
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir).expect("readable src dir") {
+        let path = entry.expect("readable dir entry").path();
+        if path.is_dir() {
+            files.extend(rust_files(&path));
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// `src/a/b.rs` -> `crate::a::b`. Returns `None` for crate roots.
+fn module_path(file: &Path) -> Option<String> {
+    let rel = file.strip_prefix("src").ok()?.with_extension("");
+    let mut parts = rel
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if parts.last().is_some_and(|p| p == "mod") {
+        parts.pop();
+    }
+    if parts.is_empty() || parts == ["lib"] || parts == ["main"] || parts[0] == "bin" {
+        return None;
+    }
+    Some(format!("crate::{}", parts.join("::")))
+}
+
+/// Finds `#[aoc(...)]`, `#[aoc_generator(...)]`, `#[aoc_stream(...)]` and
+/// `#[aoc_visual(...)]` attributes and the function they decorate.
+fn scan(text: &str, module: &str) -> Vec<(&'static str, Attr, Func)> {
+    let mut found = vec![];
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        let (attr, args) = if let Some(a) = line.strip_prefix("#[aoc_generator(") {
+            ("aoc_generator", a)
+        } else if let Some(a) = line.strip_prefix("#[aoc_stream(") {
+            ("aoc_stream", a)
+        } else if let Some(a) = line.strip_prefix("#[aoc_visual(") {
+            ("aoc_visual", a)
+        } else if let Some(a) = line.strip_prefix("#[aoc(") {
+            ("aoc", a)
+        } else {
+            continue;
+        };
+        let args = args
+            .trim_end_matches(")]")
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        let day = args[0]
+            .strip_prefix("day")
+            .and_then(|d| d.parse().ok())
+            .unwrap_or_else(|| panic!("{module}: bad day in {line}"));
+        let part = args.get(1).map(|p| {
+            p.strip_prefix("part")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or_else(|| panic!("{module}: bad part in {line}"))
+        });
+        let name = args.get(2).map(|n| n.to_string());
+
+        let mut signature = String::new();
+        for l in lines.by_ref() {
+            signature.push_str(l.trim());
+            signature.push(' ');
+            if l.contains('{') {
+                break;
+            }
+        }
+        let after_fn = signature
+            .split_once("fn ")
+            .unwrap_or_else(|| panic!("{module}: no fn after {line}"))
+            .1;
+        let fn_name = after_fn.split(['(', '<']).next().unwrap().trim();
+        let ret = match after_fn.rsplit_once("->") {
+            Some((_, r)) if r.contains("Result<") => Ret::Result,
+            Some((_, r)) if r.trim().starts_with("Option<") => Ret::Option,
+            _ => Ret::Plain,
+        };
+        let func = Func {
+            path: format!("{module}::{fn_name}"),
+            ret,
+        };
+        found.push((attr, (day, part, name), func));
+    }
+    found
+}
@@ -0,0 +1,229 @@
+//! The answer of a solver, whatever the puzzle asks for.
+//!
+//! Integers are normalised so equal values compare equal whatever type the solver
+//! returned: non-negative ones are `Unsigned`, negative ones `Signed`, and `Big` holds
+//! the decimal digits of those that fit neither.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::Grid;
+use crate::inputs::normalise;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    /// Always negative.
+    Signed(i64),
+    /// Decimal digits with an optional `-`, without leading zeros.
+    Big(String),
+    String(String),
+    /// Rendered rows, e.g. letters drawn in `#` and `.`.
+    Grid(Vec<String>),
+}
+
+/// What an answer looks like, so consumers don't have to guess from its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Unsigned,
+    Signed,
+    Big,
+    String,
+    Grid,
+}
+
+impl Answer {
+    pub fn kind(&self) -> Kind {
+        match self {
+            Answer::Unsigned(_) => Kind::Unsigned,
+            Answer::Signed(_) => Kind::Signed,
+            Answer::Big(_) => Kind::Big,
+            Answer::String(_) => Kind::String,
+            Answer::Grid(_) => Kind::Grid,
+        }
+    }
+
+    /// The answer on a single line, as in answers files. A grid is a literal `\n` followed
+    /// by each row and another `\n`, e.g. `\n#.\n.#\n`, so an empty grid is a lone `\n`.
+    /// [`FromStr`](std::str::FromStr) reads it back.
+    pub fn to_line(&self) -> String {
+        match self {
+            Answer::Grid(rows) => rows.iter().fold("\\n".to_string(), |s, r| s + r + "\\n"),
+            a => a.to_string(),
+        }
+    }
+}
+
+impl Display for Answer {
+    /// Grids are shown on their own lines.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Big(s) | Answer::String(s) => write!(f, "{s}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl std::str::FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Reads [`Answer::to_line`]. Anything that isn't an integer or a grid is a string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        Ok(
+            if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                big(s.starts_with('-'), digits)
+            } else if let Some(rows) = grid_rows(s) {
+                Answer::Grid(rows.map(str::to_string).collect())
+            } else {
+                Answer::String(s.to_string())
+            },
+        )
+    }
+}
+
+/// The rows of a grid written by [`Answer::to_line`], if `s` is one.
+fn grid_rows(s: &str) -> Option<impl Iterator<Item = &str>> {
+    let rows = s.strip_prefix("\\n")?;
+    (rows.is_empty() || rows.ends_with("\\n")).then(|| rows.split_terminator("\\n"))
+}
+
+/// The normalised answer for `digits`, negated if `negative`.
+fn big(negative: bool, digits: &str) -> Answer {
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Answer::Unsigned(0);
+    }
+    if !negative {
+        if let Ok(n) = digits.parse() {
+            return Answer::Unsigned(n);
+        }
+    } else if let Ok(n) = format!("-{digits}").parse() {
+        return Answer::Signed(n);
+    }
+    Answer::Big(format!("{}{digits}", if negative { "-" } else { "" }))
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match u64::try_from(n) {
+                    Ok(n) => Answer::Unsigned(n),
+                    Err(_) => big(false, &n.to_string()),
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                let s = n.to_string();
+                big(n < 0, s.trim_start_matches('-'))
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(c: char) -> Self {
+        Answer::String(c.to_string())
+    }
+}
+
+impl<T: Display> From<Grid<T>> for Answer {
+    fn from(grid: Grid<T>) -> Self {
+        Answer::Grid(grid.to_string().lines().map(str::to_string).collect())
+    }
+}
+
+/// Solves `input` as the runner would: line endings and trailing newlines are normalised
+/// first. Each day's public `solve_part1` and `solve_part2` call this.
+pub(crate) fn solve<T: Into<Answer>>(
+    input: &str,
+    f: impl FnOnce(&str) -> eyre::Result<T>,
+) -> eyre::Result<Answer> {
+    Ok(f(&normalise(input))?.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answer, Kind};
+    use crate::grid::Grid;
+    use eyre::Result;
+
+    #[test]
+    fn integers_are_normalised() {
+        assert_eq!(Answer::from(35i32), Answer::from(35u64));
+        assert_eq!(Answer::from(35usize), Answer::Unsigned(35));
+        assert_eq!(Answer::from(-3i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(-3i128), Answer::Signed(-3));
+        assert_eq!(Answer::from(i64::MIN), Answer::Signed(i64::MIN));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::Big("340282366920938463463374607431768211455".into())
+        );
+        assert_eq!(Answer::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(Answer::from(i128::MIN).kind(), Kind::Big);
+    }
+
+    #[test]
+    fn lines() {
+        for a in [
+            Answer::from(0u8),
+            Answer::from(-114i32),
+            Answer::from(u128::MAX),
+            Answer::from(-(u64::MAX as i128) - 1),
+            Answer::from("AB-C"),
+            Answer::Grid(vec!["#.".into(), ".#".into()]),
+            Answer::Grid(vec!["#.".into()]),
+            Answer::Grid(vec![]),
+            Answer::Grid(vec!["".into()]),
+        ] {
+            assert_eq!(a.to_line().parse::<Answer>(), Ok(a));
+        }
+        assert_eq!("007".parse::<Answer>(), Ok(Answer::Unsigned(7)));
+        assert_eq!("-0".parse::<Answer>(), Ok(Answer::Unsigned(0)));
+        assert_eq!("-".parse::<Answer>(), Ok(Answer::from("-")));
+        assert_eq!("a\\nb".parse::<Answer>(), Ok(Answer::from("a\\nb")));
+    }
+
+    #[test]
+    fn grids() -> Result<()> {
+        let grid: Grid<char> = "#.\n.#".parse()?;
+        let a = Answer::from(grid);
+        assert_eq!(a.kind(), Kind::Grid);
+        assert_eq!(a.to_string(), "#.\n.#");
+        assert_eq!(a.to_line(), "\\n#.\\n.#\\n");
+        let row = Answer::from("#.".parse::<Grid<char>>()?);
+        assert_eq!(row.to_line(), "\\n#.\\n");
+        assert_eq!(row.to_line().parse::<Answer>(), Ok(row));
+        let empty = Answer::from("".parse::<Grid<char>>()?);
+        assert_eq!(empty, Answer::Grid(vec![]));
+        assert_eq!(empty.to_line(), "\\n");
+        assert_eq!(empty.to_line().parse::<Answer>(), Ok(empty));
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+//! Known-good answers from `input/<year>/answers.txt`.
+//!
+//! Each line is `<day> <part> [<input file>] <answer>`, e.g. `day5 part1 day5-ex1.txt 35`.
+//! The input file is relative to the answers file and defaults to `day<N>.txt`. The answer
+//! is read by [`Answer`]'s `FromStr`, so a grid is written as in [`Answer::to_line`].
+
+use std::path::{Path, PathBuf};
+
+use eyre::{bail, eyre, Result, WrapErr};
+
+use crate::answer::Answer;
+use crate::runner::{self, Solver};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: PathBuf,
+    pub answer: Answer,
+    /// 1-based line in the answers file.
+    pub line: usize,
+}
+
+/// `input/<year>/answers.txt`.
+pub fn path(year: u32) -> PathBuf {
+    Path::new("input")
+        .join(year.to_string())
+        .join("answers.txt")
+}
+
+/// Appends the answers among `lines` that aren't listed yet, and returns those.
+///
+/// Fails if a line gives a different answer for a listed day, part and input.
+pub fn append(year: u32, path: &Path, lines: &[String]) -> Result<Vec<String>> {
+    let s =
+        std::fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let listed = parse(&s, year, dir).wrap_err_with(|| format!("parsing {}", path.display()))?;
+    let mut added = vec![];
+    for l in lines {
+        let new = parse_line(l, year, dir, 0).wrap_err_with(|| format!("bad answer line {l:?}"))?;
+        let same = |e: &&Expected| (e.day, e.part, &e.input) == (new.day, new.part, &new.input);
+        match listed.iter().find(same) {
+            Some(e) if e.answer != new.answer => {
+                bail!(
+                    "line {} already has answer {} for {l:?}",
+                    e.line,
+                    e.answer.to_line()
+                )
+            }
+            Some(_) => {}
+            None => added.push(l.clone()),
+        }
+    }
+    if !added.is_empty() {
+        let mut out = s;
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        for l in &added {
+            out.push_str(l);
+            out.push('\n');
+        }
+        std::fs::write(path, out).wrap_err_with(|| format!("writing {}", path.display()))?;
+    }
+    Ok(added)
+}
+
+/// The answers for `year`, or none if it has no answers file.
+pub fn load(year: u32) -> Result<Vec<Expected>> {
+    let path = path(year);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let s =
+        std::fs::read_to_string(&path).wrap_err_with(|| format!("reading {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    parse(&s, year, dir).wrap_err_with(|| format!("parsing {}", path.display()))
+}
+
+/// Parses an answers file whose input files are relative to `dir`.
+pub fn parse(s: &str, year: u32, dir: &Path) -> Result<Vec<Expected>> {
+    s.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+        .map(|(line, l)| parse_line(l, year, dir, line).wrap_err_with(|| format!("line {line}")))
+        .collect()
+}
+
+fn parse_line(l: &str, year: u32, dir: &Path, line: usize) -> Result<Expected> {
+    let fields = l.split_ascii_whitespace().collect::<Vec<_>>();
+    let (day, part, input, answer) = match fields[..] {
+        [day, part, answer] => (day, part, None, answer),
+        [day, part, input, answer] => (day, part, Some(input), answer),
+        _ => bail!("expected `<day> <part> [<input>] <answer>`"),
+    };
+    let day: u32 = day
+        .strip_prefix("day")
+        .ok_or_else(|| eyre!("bad day {day:?}"))?
+        .parse()?;
+    let part = part
+        .strip_prefix("part")
+        .ok_or_else(|| eyre!("bad part {part:?}"))?
+        .parse()?;
+    let input = dir.join(input.map_or_else(|| format!("day{day}.txt"), str::to_string));
+    Ok(Expected {
+        year,
+        day,
+        part,
+        input,
+        answer: answer.parse()?,
+        line,
+    })
+}
+
+impl Expected {
+    /// All solvers, including alternatives, that should produce this answer.
+    pub fn solvers(&self) -> impl Iterator<Item = &'static Solver> + '_ {
+        runner::select(Some(self.year), Some(self.day), Some(self.part), None)
+            .filter(|s| self.applies_to(s))
+    }
+
+    /// Whether `solver` should produce this answer. Naive solvers are only checked on
+    /// examples, as real inputs are too big for them.
+    pub fn applies_to(&self, solver: &Solver) -> bool {
+        (self.year, self.day, self.part) == (solver.year, solver.day, solver.part)
+            && (!solver.is_naive() || self.is_example())
+    }
+
+    /// Whether the input is an example, `day<N>-ex<K>.txt`.
+    pub fn is_example(&self) -> bool {
+        self.input
+            .file_name()
+            .is_some_and(|f| f.to_string_lossy().contains("-ex"))
+    }
+
+    /// Runs `solver` on the listed input and compares its answer, and the answer of its
+    /// streaming version if it has one.
+    pub fn check(&self, solver: &Solver) -> Result<()> {
+        let input = runner::read_input(&self.input)?;
+        self.compare(&solver.run(&input)?.answer)?;
+        if solver.can_stream() {
+            let mut input = runner::open_input(&self.input)?;
+            let run = solver.run_stream(&mut input).wrap_err("streaming")?;
+            self.compare(&run.answer).wrap_err("streaming")?;
+        }
+        Ok(())
+    }
+
+    fn compare(&self, answer: &Answer) -> Result<()> {
+        if *answer != self.answer {
+            bail!(
+                "expected {}, got {}",
+                self.answer.to_line(),
+                answer.to_line()
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks every solver for this day and part.
+    pub fn check_all(&self) -> Result<()> {
+        let mut any = false;
+        for solver in self.solvers() {
+            any = true;
+            self.check(solver)
+                .wrap_err_with(|| format!("{solver} on {}", self.input.display()))?;
+        }
+        if !any {
+            bail!(
+                "no solver for {} day {} part {}",
+                self.year,
+                self.day,
+                self.part
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, Expected};
+    use crate::answer::Answer;
+    use eyre::Result;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn parse_lines() -> Result<()> {
+        let s = "# comment\n\nday5 part1 35\nday5 part2 day5-ex1.txt 46\n";
+        let e = parse(s, 2023, Path::new("in"))?;
+        assert_eq!(
+            e,
+            [
+                Expected {
+                    year: 2023,
+                    day: 5,
+                    part: 1,
+                    input: PathBuf::from("in/day5.txt"),
+                    answer: Answer::Unsigned(35),
+                    line: 3,
+                },
+                Expected {
+                    year: 2023,
+                    day: 5,
+                    part: 2,
+                    input: PathBuf::from("in/day5-ex1.txt"),
+                    answer: Answer::Unsigned(46),
+                    line: 4,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn append_new_answers() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("answers.txt");
+        std::fs::write(&path, "# answers\nday9 part1 day9-ex1.txt 114")?;
+
+        let lines = ["day9 part1 day9-ex1.txt 114", "day9 part2 day9-ex1.txt 2"];
+        let added = super::append(2023, &path, &lines.map(String::from))?;
+        assert_eq!(added, ["day9 part2 day9-ex1.txt 2"]);
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "# answers\nday9 part1 day9-ex1.txt 114\nday9 part2 day9-ex1.txt 2\n"
+        );
+
+        let e = super::append(2023, &path, &["day9 part2 day9-ex1.txt 3".into()]).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3 already has answer 2 for \"day9 part2 day9-ex1.txt 3\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let e = parse("day5 part1 35\nday5 1 2\n", 2023, Path::new("")).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: bad part \"1\"");
+    }
+}
@@ -0,0 +1,337 @@
+//! Repeated timing of solvers, with summary statistics and saved baselines.
+//!
+//! A baseline holds the median generator and solver time of each solver, one
+//! `<year> <day> <part> [<name>] <generator ns> <solver ns>` line per solver, e.g.
+//! `2023 day5 part2 2104 1289`. Named baselines live in `target/bench/<name>.txt`.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use eyre::{bail, eyre, Result, WrapErr};
+
+use crate::answer::Answer;
+use crate::runner::Solver;
+
+/// Changes smaller than this are noise, whatever the relative change.
+const NOISE: Duration = Duration::from_micros(1);
+
+/// Summary of a series of timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// `None` for an empty series.
+    pub fn new(times: &[Duration]) -> Option<Self> {
+        let mut sorted = times.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let median = if runs % 2 == 1 {
+            sorted[runs / 2]
+        } else {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        };
+        let mean = sorted.iter().sum::<Duration>() / runs as u32;
+        let variance = sorted
+            .iter()
+            .map(|t| (t.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Some(Stats {
+            runs,
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            min,
+            max,
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:?} ± {:?}, median {:?}, min {:?}, max {:?} ({} runs)",
+            self.mean, self.stddev, self.median, self.min, self.max, self.runs
+        )
+    }
+}
+
+/// Identifies a solver in a baseline.
+pub type Key = (u32, u32, u32, Option<String>);
+
+fn key(solver: &Solver) -> Key {
+    (
+        solver.year,
+        solver.day,
+        solver.part,
+        solver.name.map(str::to_string),
+    )
+}
+
+/// Timings of one solver on one input.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub key: Key,
+    pub answer: Answer,
+    pub generator: Stats,
+    pub solver: Stats,
+}
+
+/// Runs the generator and the solver of `solver` `runs` times each, after one
+/// untimed warm-up run.
+pub fn measure(solver: &Solver, input: &str, runs: u32) -> Result<Measurement> {
+    solver.run(input)?;
+    let (_, generator) = time(runs, || solver.prepare(input))?;
+    let prepared = solver.prepare(input)?;
+    let (answer, solve) = time(runs, || prepared.solve().wrap_err("solver failed"))?;
+    Ok(Measurement {
+        key: key(solver),
+        answer,
+        generator,
+        solver: solve,
+    })
+}
+
+/// Runs `f` `runs` times, at least once, returning its last result and the timings.
+fn time<T>(runs: u32, f: impl Fn() -> Result<T>) -> Result<(T, Stats)> {
+    let mut times = vec![];
+    let mut last = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        last = Some(f()?);
+        times.push(start.elapsed());
+    }
+    Ok((last.unwrap(), Stats::new(&times).unwrap()))
+}
+
+/// A change in median time relative to a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Change {
+    /// Relative change, e.g. `0.25` for 25% slower.
+    pub fn ratio(&self) -> f64 {
+        if self.before.is_zero() {
+            return if self.after.is_zero() {
+                0.0
+            } else {
+                f64::INFINITY
+            };
+        }
+        self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0
+    }
+
+    /// Slower by more than `threshold` percent, and by more than the noise floor.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.after > self.before + NOISE && self.ratio() * 100.0 > threshold
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:+.1}%", self.ratio() * 100.0)
+    }
+}
+
+/// Median generator and solver times per solver.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<Key, (Duration, Duration)>);
+
+impl Baseline {
+    /// `target/bench/<name>.txt`.
+    pub fn path(name: &str) -> PathBuf {
+        Path::new("target")
+            .join("bench")
+            .join(format!("{name}.txt"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {}", path.display()))?;
+        s.parse()
+            .wrap_err_with(|| format!("parsing {}", path.display()))
+    }
+
+    /// Loads `path`, or starts an empty baseline if it doesn't exist yet.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(path, self.to_string())
+            .wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    pub fn insert(&mut self, m: &Measurement) {
+        self.0
+            .insert(m.key.clone(), (m.generator.median, m.solver.median));
+    }
+
+    /// Changes in generator and solver time, if the baseline has this solver.
+    pub fn compare(&self, m: &Measurement) -> Option<(Change, Change)> {
+        let &(generator, solver) = self.0.get(&m.key)?;
+        Some((
+            Change {
+                before: generator,
+                after: m.generator.median,
+            },
+            Change {
+                before: solver,
+                after: m.solver.median,
+            },
+        ))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((year, day, part, name), (generator, solver)) in &self.0 {
+            write!(f, "{year} day{day} part{part} ")?;
+            if let Some(name) = name {
+                write!(f, "{name} ")?;
+            }
+            writeln!(f, "{} {}", generator.as_nanos(), solver.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Baseline {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut baseline = Baseline::default();
+        for (i, l) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let (key, times) = parse_line(l).wrap_err_with(|| format!("line {}", i + 1))?;
+            baseline.0.insert(key, times);
+        }
+        Ok(baseline)
+    }
+}
+
+fn parse_line(l: &str) -> Result<(Key, (Duration, Duration))> {
+    let fields = l.split_ascii_whitespace().collect::<Vec<_>>();
+    let (year, day, part, name, generator, solver) = match fields[..] {
+        [year, day, part, generator, solver] => (year, day, part, None, generator, solver),
+        [year, day, part, name, generator, solver] => {
+            (year, day, part, Some(name), generator, solver)
+        }
+        _ => bail!("expected `<year> <day> <part> [<name>] <generator ns> <solver ns>`"),
+    };
+    let year = year.parse()?;
+    let day = day
+        .strip_prefix("day")
+        .ok_or_else(|| eyre!("bad day {day:?}"))?
+        .parse()?;
+    let part = part
+        .strip_prefix("part")
+        .ok_or_else(|| eyre!("bad part {part:?}"))?
+        .parse()?;
+    let nanos = |s: &str| -> Result<Duration> { Ok(Duration::from_nanos(s.parse()?)) };
+    Ok((
+        (year, day, part, name.map(str::to_string)),
+        (nanos(generator)?, nanos(solver)?),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{time, Baseline, Change, Measurement, Stats};
+    use crate::answer::Answer;
+    use eyre::Result;
+    use std::time::Duration;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats() {
+        let s = Stats::new(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(s.runs, 4);
+        assert_eq!(s.mean, ms(5));
+        assert_eq!(s.median, ms(5));
+        assert_eq!((s.min, s.max), (ms(2), ms(8)));
+        assert_eq!(s.stddev.as_micros(), 2236);
+        assert_eq!(Stats::new(&[ms(3), ms(1), ms(2)]).unwrap().median, ms(2));
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn change() {
+        let c = Change {
+            before: ms(100),
+            after: ms(125),
+        };
+        assert_eq!(c.to_string(), "+25.0%");
+        assert!(c.is_regression(10.0));
+        assert!(!c.is_regression(30.0));
+        let tiny = Change {
+            before: Duration::from_nanos(10),
+            after: Duration::from_nanos(50),
+        };
+        assert!(!tiny.is_regression(10.0));
+    }
+
+    #[test]
+    fn baseline() -> Result<()> {
+        let s = "2023 day5 part2 2104 1289\n2023 day11 part1 fast 5 7\n";
+        let b: Baseline = s.parse()?;
+        assert_eq!(b.to_string(), s);
+
+        let stats = Stats::new(&[Duration::from_nanos(2000)]).unwrap();
+        let m = Measurement {
+            key: (2023, 5, 2, None),
+            answer: Answer::Unsigned(0),
+            generator: stats,
+            solver: stats,
+        };
+        let (generator, solver) = b.compare(&m).unwrap();
+        assert_eq!(generator.to_string(), "-4.9%");
+        assert_eq!(solver.to_string(), "+55.2%");
+        Ok(())
+    }
+
+    /// Day 1 part 2's automaton against the version before it, which finds every digit in
+    /// a line and sorts them. `cargo test --release --lib day1_scanner -- --nocapture`
+    /// shows the timings.
+    #[test]
+    fn day1_scanner() -> Result<()> {
+        use crate::y2023::day1;
+        let input = include_str!("../input/2023/day1.txt");
+        let (answer, scanner) = time(20, || day1::part2(input))?;
+        let (expected, sorted) = time(20, || day1::part2_sorted(input))?;
+        assert_eq!(answer, expected);
+        println!("scanner: {scanner}\nsorted: {sorted}");
+        Ok(())
+    }
+
+    #[test]
+    fn baseline_error() {
+        let e = "2023 day5 part2 1 2\n2023 day5 part2 x 1 y"
+            .parse::<Baseline>()
+            .unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: invalid digit found in string");
+    }
+}
@@ -0,0 +1,224 @@
+//! Example inputs and answers extracted from a saved puzzle page.
+//!
+//! Each part of a puzzle is an `<article class="day-desc">`. Its `<pre><code>` blocks are
+//! example inputs, and the last emphasised `<code><em>` value is the part's answer for the
+//! latest example on the page. Part two usually reuses the example of part one.
+
+use eyre::{bail, Result};
+
+use crate::inputs::{normalise, Store, Variant};
+
+/// A part's description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub examples: Vec<String>,
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// From the `--- Day N: ... ---` heading.
+    pub day: Option<u32>,
+    pub parts: Vec<Part>,
+}
+
+/// A distinct example input and the answers the page gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    /// `(part, answer)`.
+    pub answers: Vec<(u32, String)>,
+}
+
+pub fn parse(html: &str) -> Page {
+    let day = html.split_once("--- Day ").and_then(|(_, rest)| {
+        let n = rest.split(':').next()?;
+        n.trim().parse().ok()
+    });
+    let mut articles = between(html, "<article", "</article>").collect::<Vec<_>>();
+    if articles.is_empty() {
+        articles.push(html);
+    }
+    let parts = articles
+        .into_iter()
+        .map(|a| Part {
+            examples: between(a, "<pre><code>", "</code></pre>")
+                .map(text)
+                .collect(),
+            answer: between(a, "<code><em>", "</em></code>").last().map(text),
+        })
+        .collect();
+    Page { day, parts }
+}
+
+impl Page {
+    /// Distinct examples in page order, each with the answers that belong to it.
+    pub fn examples(&self) -> Vec<Example> {
+        let mut examples: Vec<Example> = vec![];
+        let mut latest = None;
+        for (part, p) in (1..).zip(&self.parts) {
+            for input in &p.examples {
+                let input = normalise(input);
+                latest = Some(match examples.iter().position(|e| e.input == input) {
+                    Some(i) => i,
+                    None => {
+                        examples.push(Example {
+                            input,
+                            answers: vec![],
+                        });
+                        examples.len() - 1
+                    }
+                });
+            }
+            if let (Some(i), Some(answer)) = (latest, &p.answer) {
+                examples[i].answers.push((part, answer.clone()));
+            }
+        }
+        examples
+    }
+}
+
+/// Writes the examples as `day<N>-ex<K>.txt`, numbered in page order, and returns the
+/// answers file lines for them. Files that already hold the same example are kept, and
+/// empty placeholders are filled in.
+pub fn save(examples: &[Example], store: &Store, year: u32, day: u32) -> Result<Vec<String>> {
+    let mut lines = vec![];
+    for (k, example) in (1..).zip(examples) {
+        let variant = Variant::Example(k);
+        let path = store.path(year, day, variant);
+        if store.is_cached(year, day, variant) {
+            if store.read(year, day, variant)? != example.input {
+                bail!(
+                    "{} already exists with a different example, not overwriting it",
+                    path.display()
+                );
+            }
+        } else {
+            store.save(year, day, variant, &example.input)?;
+        }
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        for (part, answer) in &example.answers {
+            lines.push(format!("day{day} part{part} {file} {answer}"));
+        }
+    }
+    Ok(lines)
+}
+
+/// The parts of `s` between each `start` and the following `end`.
+fn between<'a>(s: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        let (_, after) = rest.split_once(start)?;
+        let (inside, after) = after.split_once(end)?;
+        rest = after;
+        Some(inside)
+    })
+}
+
+/// The text of an HTML fragment: tags removed and entities decoded.
+fn text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some((before, after)) = rest.split_once('<') {
+        out.push_str(before);
+        rest = after.split_once('>').map_or("", |(_, a)| a);
+    }
+    out.push_str(rest);
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, save, Example, Part};
+    use crate::inputs::{Store, Variant};
+    use eyre::Result;
+
+    const PAGE: &str = r#"<html><head><title>Day 9 - Advent of Code 2023</title></head><body>
+<main>
+<article class="day-desc"><h2>--- Day 9: Mirage Maintenance ---</h2>
+<p>For example:</p>
+<pre><code>0 3 6
+1 3 6 10
+</code></pre>
+<p>A bigger one, where <code>a &lt; b</code>:</p>
+<pre><code>0 <em>3</em> 6 9
+10 13 16
+</code></pre>
+<p>Adding them up gives <code><em>114</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1992273652</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the same example:</p>
+<pre><code>10 13 16
+</code></pre>
+<pre><code>0 3 6
+1 3 6 10
+</code></pre>
+<p>The sum is <code><em>2</em></code>, not <code><em>-3</em></code>, oops, it is <code><em>2</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn parse_page() {
+        let page = parse(PAGE);
+        assert_eq!(page.day, Some(9));
+        assert_eq!(
+            page.parts,
+            [
+                Part {
+                    examples: vec!["0 3 6\n1 3 6 10\n".into(), "0 3 6 9\n10 13 16\n".into()],
+                    answer: Some("114".into()),
+                },
+                Part {
+                    examples: vec!["10 13 16\n".into(), "0 3 6\n1 3 6 10\n".into()],
+                    answer: Some("2".into()),
+                },
+            ]
+        );
+        assert_eq!(super::text("a &lt;<em>b</em>&amp;&gt; c"), "a <b&> c");
+    }
+
+    #[test]
+    fn examples() {
+        let examples = parse(PAGE).examples();
+        let inputs = examples
+            .iter()
+            .map(|e| e.input.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, ["0 3 6\n1 3 6 10", "0 3 6 9\n10 13 16", "10 13 16"]);
+        assert_eq!(examples[0].answers, [(2, "2".to_string())]);
+        assert_eq!(examples[1].answers, [(1, "114".to_string())]);
+        assert!(examples[2].answers.is_empty());
+    }
+
+    #[test]
+    fn save_examples() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = Store::new(&dir);
+        let examples = parse(PAGE).examples();
+
+        let lines = save(&examples, &store, 2023, 9)?;
+        assert_eq!(
+            lines,
+            ["day9 part2 day9-ex1.txt 2", "day9 part1 day9-ex2.txt 114"]
+        );
+        assert_eq!(store.read(2023, 9, Variant::Example(3))?, "10 13 16");
+
+        // Saving the same examples again is fine, different ones are not.
+        assert_eq!(save(&examples, &store, 2023, 9)?, lines);
+        let other = Example {
+            input: "1 2 3".into(),
+            answers: vec![],
+        };
+        let e = save(&[other], &store, 2023, 9).unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("with a different example, not overwriting it"));
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+//! Fuzz targets, run by libFuzzer from the cargo-fuzz crate in `fuzz/`, or offline by
+//! `tests/fuzz.rs` on the cached inputs and mutations of them.
+//!
+//! Inputs are arbitrary bytes. Solvers may fail on them but must not panic, and values
+//! that parse must print as text that parses back to the same value.
+
+use std::fmt::{Debug, Display};
+use std::path::Path;
+use std::str::FromStr;
+
+use eyre::{Result, WrapErr};
+
+use crate::grid::Grid;
+use crate::runner::{SOLVERS, VISUALS};
+use crate::y2023::{day10, day2, day4, day5, day6, day7part1, day7part2, day8};
+
+/// Every solver except the naive ones, which only promise to handle small inputs, their
+/// streaming versions and the visualisations.
+pub fn solvers(data: &[u8]) {
+    for solver in SOLVERS.iter().filter(|s| s.can_stream()) {
+        let _ = solver.run_stream(&mut &data[..]);
+    }
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for solver in SOLVERS.iter().filter(|s| !s.is_naive()) {
+        let _ = solver.run(input);
+    }
+    for visual in VISUALS {
+        let _ = visual.draw(input);
+    }
+}
+
+/// Parsing, printing and parsing again gives the same value, for every type with both.
+pub fn round_trips(data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    round_trip::<day2::Draws>(input);
+    round_trip::<Grid<char>>(input);
+    round_trip::<day5::Input>(input);
+    round_trip::<day5::Map>(input);
+    round_trip::<day6::Input1>(input);
+    round_trip::<day8::Input>(input);
+    round_trip::<day10::Map>(input);
+    for line in input.lines() {
+        round_trip::<day2::Game>(line);
+        round_trip::<day4::Card>(line);
+        round_trip::<day7part1::Entry>(line);
+        round_trip::<day7part2::Entry>(line);
+        round_trip::<day5::MapEntry>(line);
+        round_trip::<day8::Node>(line);
+    }
+}
+
+fn round_trip<T>(s: &str)
+where
+    T: FromStr + Display + PartialEq + Debug,
+{
+    let Ok(value) = s.parse::<T>() else {
+        return;
+    };
+    let shown = value.to_string();
+    match shown.parse::<T>() {
+        Ok(again) => {
+            assert_eq!(again, value, "{s:?} printed as {shown:?}");
+            assert_eq!(again.to_string(), shown, "{s:?} printed as {shown:?}");
+        }
+        Err(_) => panic!("{s:?} printed as {shown:?}, which doesn't parse"),
+    }
+}
+
+/// Every cached input under `root`, e.g. `input/2023/day5.txt`, to start fuzzing from.
+pub fn corpus(root: &Path) -> Result<Vec<Vec<u8>>> {
+    let mut files = vec![];
+    for dir in std::fs::read_dir(root).wrap_err_with(|| format!("reading {}", root.display()))? {
+        let dir = dir?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        for file in std::fs::read_dir(&dir)? {
+            let file = file?.path();
+            if file.extension().is_some_and(|e| e == "txt") {
+                files.push(file);
+            }
+        }
+    }
+    files.sort();
+    files
+        .iter()
+        .map(|f| std::fs::read(f).wrap_err_with(|| format!("reading {}", f.display())))
+        .collect()
+}
+
+/// Bytes that mean something to at least one parser.
+const INTERESTING: &[u8] = b"0123456789 \n\r:;,.-=()|#*LRJF7SAZ";
+
+/// Deterministic mutations of corpus entries, for fuzzing without libFuzzer.
+pub struct Mutator {
+    state: u64,
+}
+
+impl Mutator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    /// xorshift64.
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    /// A few random edits of `seed`, starting from its first lines so that headers like
+    /// `seeds:` survive and inputs stay small. Some edits repeat a word or a line, as sums
+    /// only overflow on many lines of big or repeated numbers.
+    pub fn mutate(&mut self, seed: &[u8]) -> Vec<u8> {
+        let lines = 1 + self.below(12);
+        let end = seed
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .nth(lines)
+            .map_or(seed.len(), |(i, _)| i);
+        let mut data = seed[..end].to_vec();
+        for _ in 0..1 + self.below(4) {
+            let at = self.below(data.len() + 1);
+            match self.below(12) {
+                0 if at < data.len() => data[at] = INTERESTING[self.below(INTERESTING.len())],
+                1 => data.insert(at, INTERESTING[self.below(INTERESTING.len())]),
+                2 => {
+                    let end = (at + 1 + self.below(8)).min(data.len());
+                    data.drain(at..end);
+                }
+                3 => {
+                    let end = (at + 1 + self.below(8)).min(data.len());
+                    let copy = data[at..end].to_vec();
+                    let to = self.below(data.len() + 1);
+                    data.splice(to..to, copy);
+                }
+                4 => data.truncate(at),
+                5 => {
+                    let n = ["0", "9", "4294967295", "4294967296", "18446744073709551615"];
+                    let n = n[self.below(n.len())].bytes();
+                    data.splice(at..at, n);
+                }
+                6 => {
+                    self.repeat_word(&mut data, at);
+                }
+                7 => self.repeat_line(&mut data, at),
+                _ => {
+                    // A line full of the same number, many times, to reach sums that
+                    // overflow.
+                    let at = self.repeat_word(&mut data, at);
+                    self.repeat_line(&mut data, at);
+                }
+            }
+        }
+        data
+    }
+
+    /// Repeats the word around `at`, returning where it starts.
+    fn repeat_word(&mut self, data: &mut Vec<u8>, at: usize) -> usize {
+        self.repeat(data, at, |b| b.is_ascii_whitespace(), b' ')
+    }
+
+    /// Repeats the line around `at`.
+    fn repeat_line(&mut self, data: &mut Vec<u8>, at: usize) {
+        self.repeat(data, at, |b| b == b'\n', b'\n');
+    }
+
+    /// Repeats the part of `data` around `at` between separators up to 64 times, enough
+    /// to overflow sums of powers of two or of small numbers, but adding no more than
+    /// 4 KiB so that grids stay small. Returns where the part starts.
+    fn repeat(&mut self, data: &mut Vec<u8>, at: usize, sep: fn(u8) -> bool, join: u8) -> usize {
+        let at = at.min(data.len());
+        let start = data[..at]
+            .iter()
+            .rposition(|&b| sep(b))
+            .map_or(0, |i| i + 1);
+        let end = data[at..]
+            .iter()
+            .position(|&b| sep(b))
+            .map_or(data.len(), |i| at + i);
+        let mut part = data[start..end].to_vec();
+        part.push(join);
+        let times = self.below(64).min(4096 / part.len());
+        data.splice(start..start, part.repeat(times));
+        start
+    }
+}
@@ -0,0 +1,436 @@
+use std::fmt::Display;
+use std::ops::{Add, Index, IndexMut, Sub};
+
+use eyre::eyre;
+use itertools::Itertools;
+
+use crate::parse::{self, error_at};
+
+/// Signed grid coordinate. May point outside of the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// Unsigned grid coordinate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UPos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(&self, dir: Dir) -> Pos {
+        *self + dir.offset()
+    }
+
+    pub fn manhattan(&self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl UPos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Pos {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Pos) -> Pos {
+        Pos::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl From<UPos> for Pos {
+    fn from(p: UPos) -> Self {
+        Pos::new(p.x as isize, p.y as isize)
+    }
+}
+
+impl TryFrom<Pos> for UPos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: Pos) -> Result<Self, Self::Error> {
+        Ok(UPos::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+/// Anything that can be used to address a cell in a [`Grid`].
+pub trait Coord: Copy {
+    fn to_upos(self) -> Option<UPos>;
+}
+
+impl Coord for UPos {
+    fn to_upos(self) -> Option<UPos> {
+        Some(self)
+    }
+}
+
+impl Coord for Pos {
+    fn to_upos(self) -> Option<UPos> {
+        self.try_into().ok()
+    }
+}
+
+/// The four cardinal directions. `N` is towards lower `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir {
+    pub const ALL: [Self; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+    pub fn offset(&self) -> Pos {
+        match self {
+            Dir::N => Pos::new(0, -1),
+            Dir::E => Pos::new(1, 0),
+            Dir::S => Pos::new(0, 1),
+            Dir::W => Pos::new(-1, 0),
+        }
+    }
+
+    pub fn inv(&self) -> Dir {
+        match self {
+            Dir::N => Dir::S,
+            Dir::S => Dir::N,
+            Dir::E => Dir::W,
+            Dir::W => Dir::E,
+        }
+    }
+}
+
+/// Offsets of the 8-neighbourhood, row by row.
+const NEIGHBOURS8: [Pos; 8] = [
+    Pos::new(-1, -1),
+    Pos::new(0, -1),
+    Pos::new(1, -1),
+    Pos::new(-1, 0),
+    Pos::new(1, 0),
+    Pos::new(-1, 1),
+    Pos::new(0, 1),
+    Pos::new(1, 1),
+];
+
+/// Rectangular grid stored row by row in a flat buffer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            data: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Returns the grid mirrored along its main diagonal.
+    pub fn transpose(&self) -> Self {
+        let data = self.columns().flat_map(|c| c.cloned()).collect_vec();
+        Self {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Returns the grid rotated a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let data = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned().collect_vec())
+            .collect_vec();
+        Self {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Returns the grid rotated a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let data = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned().collect_vec())
+            .collect_vec();
+        Self {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows. All rows must have the same length. Empty rows make an
+    /// empty grid, so that it displays as text that parses back to it.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> eyre::Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = if width == 0 { 0 } else { rows.len() };
+        if let Some((y, row)) = rows.iter().find_position(|r| r.len() != width) {
+            return Err(eyre!(
+                "line {}: expected {} columns, found {}",
+                y + 1,
+                width,
+                row.len()
+            ));
+        }
+        let data = rows.into_iter().flatten().collect();
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid with one character per cell using `f` to map characters.
+    ///
+    /// Errors name the (1-based) line and column of the offending character.
+    pub fn parse_with<E, F>(s: &str, mut f: F) -> eyre::Result<Self>
+    where
+        E: Display,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let rows = parse::lines(s, |l| {
+            l.char_indices()
+                .map(|(i, c)| {
+                    f(c).map_err(|e| error_at(l, &l[i..], format!("bad cell {c:?}: {e}")))
+                })
+                .collect()
+        })?;
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of<C: Coord>(&self, p: C) -> Option<usize> {
+        let p = p.to_upos()?;
+        (p.x < self.width && p.y < self.height).then_some(p.y * self.width + p.x)
+    }
+
+    pub fn contains<C: Coord>(&self, p: C) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    pub fn get<C: Coord>(&self, p: C) -> Option<&T> {
+        self.index_of(p).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut<C: Coord>(&mut self, p: C) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.data[i])
+    }
+
+    /// Replaces the cell at `p`, returning the old value, or `None` if `p` is outside.
+    pub fn set<C: Coord>(&mut self, p: C, value: T) -> Option<T> {
+        self.get_mut(p).map(|c| std::mem::replace(c, value))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        // `chunks` panics on a zero chunk size.
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = UPos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| UPos::new(x, y)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = (UPos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    /// Position of the first cell (in row-major order) matching `pred`.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<UPos> {
+        self.cells().find(|(_, t)| pred(t)).map(|(p, _)| p)
+    }
+
+    /// In-bounds positions north, east, south and west of `p`.
+    pub fn neighbours4<C: Coord + Into<Pos>>(&self, p: C) -> impl Iterator<Item = UPos> + '_ {
+        let p: Pos = p.into();
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |d| p.step(d).to_upos().filter(|&n| self.contains(n)))
+    }
+
+    /// In-bounds positions surrounding `p`, including diagonals.
+    pub fn neighbours8<C: Coord + Into<Pos>>(&self, p: C) -> impl Iterator<Item = UPos> + '_ {
+        let p: Pos = p.into();
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |o| (p + o).to_upos().filter(|&n| self.contains(n)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<UPos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: UPos) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} outside of {}x{}", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<UPos> for Grid<T> {
+    fn index_mut(&mut self, p: UPos) -> &mut T {
+        let (w, h) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} outside of {w}x{h}"))
+    }
+}
+
+impl<T> std::str::FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, T::try_from)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let formatter = self
+            .rows()
+            .format_with("\n", |row, g| g(&row.iter().format("")));
+        write!(f, "{formatter}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dir, Grid, Pos, UPos};
+    use eyre::Result;
+    use itertools::Itertools;
+
+    const DATA: &str = "abc\ndef";
+
+    #[test]
+    fn parse_and_display() -> Result<()> {
+        let g: Grid<char> = DATA.parse()?;
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[UPos::new(2, 1)], 'f');
+        assert_eq!(g.to_string(), DATA);
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let e = "ab\nc".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(e.to_string(), "line 2: expected 2 columns, found 1");
+    }
+
+    #[test]
+    fn empty() -> Result<()> {
+        let g: Grid<char> = "\n\n".parse()?;
+        assert_eq!((g.width(), g.height()), (0, 0));
+        assert_eq!(g, "".parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_cell() {
+        let e = Grid::parse_with("..\n.x", |c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err("not . or #"),
+        })
+        .unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 2: bad cell 'x': not . or #");
+    }
+
+    #[test]
+    fn signed_access() -> Result<()> {
+        let g: Grid<char> = DATA.parse()?;
+        assert_eq!(g.get(Pos::new(-1, 0)), None);
+        assert_eq!(g.get(Pos::new(3, 0)), None);
+        assert_eq!(g.get(Pos::new(0, 0).step(Dir::S)), Some(&'d'));
+        Ok(())
+    }
+
+    #[test]
+    fn neighbours() -> Result<()> {
+        let g: Grid<char> = DATA.parse()?;
+        let n4 = g.neighbours4(UPos::new(0, 0)).map(|p| g[p]).collect_vec();
+        assert_eq!(n4, ['b', 'd']);
+        let n8 = g.neighbours8(UPos::new(1, 1)).map(|p| g[p]).collect_vec();
+        assert_eq!(n8, ['a', 'b', 'c', 'd', 'f']);
+        Ok(())
+    }
+
+    #[test]
+    fn rows_and_columns() -> Result<()> {
+        let g: Grid<char> = DATA.parse()?;
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        let cols = g.columns().map(|c| c.collect::<String>()).collect_vec();
+        assert_eq!(cols, ["ad", "be", "cf"]);
+        Ok(())
+    }
+
+    #[test]
+    fn transform() -> Result<()> {
+        let g: Grid<char> = DATA.parse()?;
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        Ok(())
+    }
+}
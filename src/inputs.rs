@@ -0,0 +1,342 @@
+//! Puzzle inputs cached under `input/<year>/`, and fetching them from the puzzle site.
+//!
+//! The real input for a day is `day<N>.txt` and examples are `day<N>-ex<K>.txt`, numbered
+//! from 1. Cached files are never overwritten, but empty ones are placeholders, e.g. from
+//! `new-day`, and can be filled in.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use eyre::{bail, eyre, Result, WrapErr};
+
+/// Which input of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Variant {
+    #[default]
+    Real,
+    /// 1-based example number.
+    Example(u32),
+}
+
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Real => write!(f, "real"),
+            Variant::Example(n) => write!(f, "ex{n}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Variant {
+    type Err = eyre::Report;
+
+    /// Accepts `real`, `ex<K>`, `example<K>` and a plain `<K>`. A missing `K` means 1.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "real" {
+            return Ok(Variant::Real);
+        }
+        let n = s
+            .strip_prefix("example")
+            .or_else(|| s.strip_prefix("ex"))
+            .unwrap_or(s);
+        if n.is_empty() {
+            return Ok(Variant::Example(1));
+        }
+        match n.parse() {
+            Ok(0) | Err(_) => bail!("bad input variant {s:?}, expected `real` or `ex<N>`"),
+            Ok(n) => Ok(Variant::Example(n)),
+        }
+    }
+}
+
+/// Unix line endings and no trailing newlines, however the input was saved.
+pub fn normalise(s: &str) -> String {
+    s.replace("\r\n", "\n")
+        .trim_end_matches(['\n', '\r'])
+        .to_string()
+}
+
+/// The directory tree holding cached inputs.
+#[derive(Debug, Clone)]
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Default for Store {
+    /// `input/` in the working directory.
+    fn default() -> Self {
+        Self::new("input")
+    }
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `<root>/<year>/day<N>.txt` or `<root>/<year>/day<N>-ex<K>.txt`.
+    pub fn path(&self, year: u32, day: u32, variant: Variant) -> PathBuf {
+        let file = match variant {
+            Variant::Real => format!("day{day}.txt"),
+            Variant::Example(n) => format!("day{day}-ex{n}.txt"),
+        };
+        self.root.join(year.to_string()).join(file)
+    }
+
+    pub fn read(&self, year: u32, day: u32, variant: Variant) -> Result<String> {
+        let path = self.path(year, day, variant);
+        let s = std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("reading {}", path.display()))?;
+        Ok(normalise(&s))
+    }
+
+    /// Whether the input exists and isn't an empty placeholder.
+    pub fn is_cached(&self, year: u32, day: u32, variant: Variant) -> bool {
+        is_cached(&self.path(year, day, variant))
+    }
+
+    /// Writes a new input file, normalised and with a single trailing newline.
+    /// Fails if the input is already cached.
+    pub fn save(&self, year: u32, day: u32, variant: Variant, contents: &str) -> Result<PathBuf> {
+        let path = self.path(year, day, variant);
+        create_parent(&path)?;
+        let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(f) => f,
+            // A placeholder is filled in, as long as it is still empty once opened.
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                let f = OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .wrap_err_with(|| format!("opening {}", path.display()))?;
+                if f.metadata().is_ok_and(|m| m.len() > 0) {
+                    bail!("{} already exists, not overwriting it", path.display());
+                }
+                f
+            }
+            Err(e) => return Err(e).wrap_err_with(|| format!("creating {}", path.display())),
+        };
+        writeln!(file, "{}", normalise(contents))
+            .wrap_err_with(|| format!("writing {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Creates an empty placeholder for the input, unless the file exists.
+    /// Returns whether it was created.
+    pub fn placeholder(&self, year: u32, day: u32, variant: Variant) -> Result<bool> {
+        let path = self.path(year, day, variant);
+        if path.exists() {
+            return Ok(false);
+        }
+        create_parent(&path)?;
+        std::fs::write(&path, "").wrap_err_with(|| format!("creating {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Downloads the real input for a day, unless it is already cached.
+    pub fn fetch(&self, client: &Client, year: u32, day: u32) -> Result<PathBuf> {
+        let path = self.path(year, day, Variant::Real);
+        if is_cached(&path) {
+            bail!("{} already exists, not overwriting it", path.display());
+        }
+        let input = client.input(year, day)?;
+        self.save(year, day, Variant::Real, &input)
+    }
+}
+
+fn is_cached(path: &Path) -> bool {
+    path.metadata().is_ok_and(|m| m.len() > 0)
+}
+
+fn create_parent(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// Downloads inputs from the puzzle site, or anything that serves the same paths.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub const DEFAULT_URL: &'static str = "https://adventofcode.com";
+
+    /// `session` is the value of the site's `session` cookie.
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// `GET <base url>/<year>/day/<day>/input`.
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set(
+                "User-Agent",
+                concat!("aoc-2023/", env!("CARGO_PKG_VERSION")),
+            )
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code @ (400 | 401), _) => {
+                    eyre!("HTTP {code} from {url}, is the session token valid?")
+                }
+                ureq::Error::Status(code, _) => eyre!("HTTP {code} from {url}"),
+                e => eyre!(e),
+            })?;
+        response
+            .into_string()
+            .wrap_err_with(|| format!("reading response from {url}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{normalise, Client, Store, Variant};
+    use eyre::Result;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::JoinHandle;
+
+    /// A fresh directory, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("aoc-inputs-{}-{name}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+
+        fn store(&self) -> Store {
+            Store::new(&self.0)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Answers one request with `status` and `body`, and returns its request line
+    /// and headers.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = BufReader::new(&stream)
+                .lines()
+                .map(|l| l.unwrap())
+                .take_while(|l| !l.is_empty())
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn variants() -> Result<()> {
+        assert_eq!("real".parse::<Variant>()?, Variant::Real);
+        assert_eq!("ex".parse::<Variant>()?, Variant::Example(1));
+        assert_eq!("example".parse::<Variant>()?, Variant::Example(1));
+        assert_eq!("ex3".parse::<Variant>()?, Variant::Example(3));
+        assert_eq!("2".parse::<Variant>()?, Variant::Example(2));
+        assert!("ex0".parse::<Variant>().is_err());
+        assert!("exx".parse::<Variant>().is_err());
+
+        let store = Store::new("in");
+        assert_eq!(
+            store.path(2023, 5, Variant::Real),
+            PathBuf::from("in/2023/day5.txt")
+        );
+        assert_eq!(
+            store.path(2023, 10, "ex2".parse()?),
+            PathBuf::from("in/2023/day10-ex2.txt")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn normalises() {
+        assert_eq!(normalise("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalise("a\nb\n"), "a\nb");
+        assert_eq!(normalise("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn save_refuses_to_overwrite() -> Result<()> {
+        let dir = TempDir::new("save");
+        let store = dir.store();
+        let path = store.save(2023, 1, Variant::Example(1), "1\r\n2\r\n\r\n")?;
+        assert_eq!(std::fs::read_to_string(&path)?, "1\n2\n");
+        assert_eq!(store.read(2023, 1, Variant::Example(1))?, "1\n2");
+
+        let e = store.save(2023, 1, Variant::Example(1), "3").unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("already exists, not overwriting it"));
+        assert_eq!(std::fs::read_to_string(&path)?, "1\n2\n");
+
+        // Placeholders are filled in, but never replace anything.
+        assert!(store.placeholder(2023, 1, Variant::Real)?);
+        assert!(!store.placeholder(2023, 1, Variant::Example(1))?);
+        assert!(!store.is_cached(2023, 1, Variant::Real));
+        store.save(2023, 1, Variant::Real, "4")?;
+        assert_eq!(store.read(2023, 1, Variant::Real)?, "4");
+        assert!(!store.placeholder(2023, 1, Variant::Real)?);
+        Ok(())
+    }
+
+    #[test]
+    fn fetch() -> Result<()> {
+        let (url, server) = serve_once("200 OK", "1 2\r\n3 4\r\n");
+        let dir = TempDir::new("fetch");
+        let store = dir.store();
+        let client = Client::new(&url, "secret\n");
+        let path = store.fetch(&client, 2023, 9)?;
+        assert_eq!(std::fs::read_to_string(path)?, "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2023/day/9/input HTTP/1.1");
+        assert!(request.iter().any(|h| h == "Cookie: session=secret"));
+
+        // Cached, so the server isn't contacted again.
+        let e = store.fetch(&client, 2023, 9).unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("already exists, not overwriting it"));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_bad_session() {
+        let (url, server) = serve_once("400 Bad Request", "Puzzle inputs differ by user.");
+        let dir = TempDir::new("bad-session");
+        let store = dir.store();
+        let e = store
+            .fetch(&Client::new(&url, "expired"), 2023, 9)
+            .unwrap_err();
+        server.join().unwrap();
+        assert_eq!(
+            e.to_string(),
+            format!("HTTP 400 from {url}/2023/day/9/input, is the session token valid?")
+        );
+        assert!(!store.path(2023, 9, Variant::Real).exists());
+    }
+}
@@ -1,14 +1,19 @@
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7part1;
-pub mod day7part2;
-pub mod day8;
-pub mod day9;
+//! Advent of Code solutions, one module per year with one module per day, e.g.
+//! `y2023::day5`. The modules at the top are shared by every year.
 
-aoc_runner_derive::aoc_lib! { year = 2023 }
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod examples;
+pub mod fuzz;
+pub mod grid;
+pub mod inputs;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod stream;
+pub mod summary;
+pub mod trace;
+pub mod y2023;
@@ -1 +1,573 @@
-aoc_runner_derive::aoc_main! { lib = aoc_2023 }
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use aoc_2023::answer::Kind;
+use aoc_2023::bench::{self, Baseline};
+use aoc_2023::inputs::{Client, Store, Variant};
+use aoc_2023::report::{self, Record};
+use aoc_2023::runner::{self, Run, Solver, Visual};
+use aoc_2023::trace;
+use aoc_2023::y2023::day1::{self, Vocabulary};
+use aoc_2023::y2023::day2::{self, Bag};
+use aoc_2023::{answers, examples, render, scaffold, summary};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use eyre::{bail, eyre, Result, WrapErr};
+
+/// Advent of Code solutions.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Defaults to every year, or the latest one when a day is given or a file is
+    /// written.
+    #[arg(short, long, global = true)]
+    year: Option<u32>,
+    /// Show what the solvers of some days do, step by step, on stderr: `day10`, or
+    /// `day10=debug` for less detail, several separated by commas, or `all`.
+    #[arg(long, global = true, env = "AOC_TRACE", value_name = "DAYS")]
+    trace: Option<trace::Spec>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+impl Cli {
+    /// The selected year, or the latest one.
+    fn year(&self) -> u32 {
+        self.year.unwrap_or_else(runner::latest_year)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the selected puzzles and print the answers.
+    Run(RunArgs),
+    /// Run the selected solvers repeatedly and report timings.
+    Bench(BenchArgs),
+    /// Compare the selected solvers with the known-good answers in
+    /// `input/<year>/answers.txt`.
+    Check(Selection),
+    /// Save the examples and answers in a saved puzzle page as example inputs and
+    /// entries in `input/<year>/answers.txt`.
+    Examples {
+        /// The puzzle's HTML page, as saved from the browser.
+        page: PathBuf,
+        /// Defaults to the day in the page's heading.
+        #[arg(short, long)]
+        day: Option<u32>,
+    },
+    /// Start a new day: a module with `gen`, `part1` and `part2` in `src/y<year>/`, and empty
+    /// input files.
+    NewDay { day: u32 },
+    /// Download real inputs that aren't cached yet.
+    Fetch {
+        /// Only this day. Defaults to every day with a solver.
+        #[arg(short, long)]
+        day: Option<u32>,
+        /// Value of the site's `session` cookie.
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: String,
+        #[arg(long, env = "AOC_URL", default_value = Client::DEFAULT_URL)]
+        url: String,
+    },
+}
+
+#[derive(Args)]
+struct Selection {
+    #[arg(short, long)]
+    day: Option<u32>,
+    #[arg(short, long)]
+    part: Option<u32>,
+    /// Only run the alternative solution with this name.
+    #[arg(short, long)]
+    name: Option<String>,
+    /// Input file, or `-` for stdin. Defaults to `input/<year>/day<N>.txt`.
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+    /// Use the cached example input, `ex<K>` or just `K`, instead of the real one.
+    #[arg(short, long, conflicts_with = "input")]
+    example: Option<Variant>,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// Run the selected solvers in parallel and print a summary table. Failures are
+    /// reported in the table without stopping the others.
+    #[arg(short, long, conflicts_with = "input")]
+    all: bool,
+    /// Threads for `--all`. Defaults to the number of CPUs.
+    #[arg(short = 'j', long, requires = "all")]
+    threads: Option<usize>,
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Solve in a single pass over the input, for inputs too big to hold in memory. Only
+    /// solvers with an `#[aoc_stream]` version are run.
+    #[arg(short, long, conflicts_with = "all")]
+    stream: bool,
+    /// Also draw what the day's solvers do with the input: in the terminal for `-`, or as a
+    /// `.txt` file with terminal colours, a `.ppm` image or an `.svg` image.
+    #[arg(long, value_name = "PATH", requires = "day", conflicts_with_all = ["all", "stream"])]
+    visualize: Option<PathBuf>,
+    /// Words that count as digits in day 1 part 2, besides the digits: `english`,
+    /// `swedish`, `german`, `roman`, or a file with a word and its digit per line.
+    #[arg(
+        long,
+        value_name = "NAME|PATH",
+        requires = "day",
+        conflicts_with = "all"
+    )]
+    vocabulary: Option<String>,
+    /// The most cubes of each colour for day 2, e.g. `red=12,green=13,blue=14`, the default.
+    #[arg(long, requires = "day", conflicts_with = "all")]
+    bag: Option<Bag>,
+    /// Before solving day 1, show what part 2 makes of each line: the words found, the first
+    /// and last, and the lines without a value.
+    #[arg(long, requires = "day", conflicts_with_all = ["all", "stream", "format"])]
+    diagnose: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    /// An array of results, see `aoc_2023::report::Record`.
+    Json,
+    /// One JSON result per line.
+    Jsonl,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// Number of timed runs of the generator and of the solver.
+    #[arg(short, long, default_value_t = 20)]
+    repeat: u32,
+    /// Save the median times as the named baseline in `target/bench/`.
+    #[arg(long, value_name = "NAME")]
+    save: Option<String>,
+    /// Compare with the named baseline and fail on regressions.
+    #[arg(short, long, value_name = "NAME")]
+    baseline: Option<String>,
+    /// Slowdown, in percent of the baseline median, that counts as a regression.
+    #[arg(short, long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+impl Selection {
+    /// The selected solvers. Naive ones are left out on real inputs, which are too big for
+    /// them, unless asked for by name.
+    fn solvers(&self, year: Option<u32>) -> Result<Vec<&'static Solver>> {
+        let small = self.example.is_some() || self.name.is_some();
+        let solvers = self.all_solvers(year)?;
+        let solvers: Vec<_> = solvers
+            .into_iter()
+            .filter(|s| small || !s.is_naive())
+            .collect();
+        if solvers.is_empty() {
+            bail!("only naive solvers match the selection, use `-e` or `-n naive`");
+        }
+        Ok(solvers)
+    }
+
+    /// The selected solvers, naive ones included.
+    fn all_solvers(&self, year: Option<u32>) -> Result<Vec<&'static Solver>> {
+        let year = year.or_else(|| self.day.map(|_| runner::latest_year()));
+        let solvers: Vec<_> =
+            runner::select(year, self.day, self.part, self.name.as_deref()).collect();
+        if solvers.is_empty() {
+            bail!("no solver matches the selection");
+        }
+        Ok(solvers)
+    }
+}
+
+/// Reads each day's input once, so stdin can be shared by both parts.
+struct Inputs<'a> {
+    path: Option<&'a PathBuf>,
+    variant: Variant,
+    cache: HashMap<(u32, u32), String>,
+}
+
+impl<'a> Inputs<'a> {
+    fn new(selection: &'a Selection) -> Self {
+        Self {
+            path: selection.input.as_ref(),
+            variant: selection.example.unwrap_or_default(),
+            cache: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, solver: &Solver) -> Result<&str> {
+        let key = (solver.year, solver.day);
+        if !self.cache.contains_key(&key) {
+            let input = runner::read_input(&self.path(solver))?;
+            self.cache.insert(key, input);
+        }
+        Ok(&self.cache[&key])
+    }
+
+    /// The input file for `solver`.
+    fn path(&self, solver: &Solver) -> PathBuf {
+        self.path
+            .cloned()
+            .unwrap_or_else(|| solver.default_input(self.variant))
+    }
+}
+
+fn run(args: &RunArgs, year: Option<u32>) -> Result<()> {
+    let selection = &args.selection;
+    if args.all {
+        return run_all(args, year);
+    }
+    let mut solvers = selection.solvers(year)?;
+    if args.vocabulary.is_some() {
+        only_day(&solvers, 1, "--vocabulary")?;
+    }
+    if args.bag.is_some() {
+        only_day(&solvers, 2, "--bag")?;
+    }
+    let setting = match (&args.vocabulary, &args.bag) {
+        (Some(name), _) => Some(Setting::Vocabulary(Vocabulary::load(name)?)),
+        (_, Some(bag)) => Some(Setting::Bag(bag.clone())),
+        _ => None,
+    };
+    if args.stream {
+        solvers.retain(|s| s.can_stream());
+        if solvers.is_empty() {
+            bail!("no selected solver has a streaming version");
+        }
+        if selection.input.as_deref() == Some(Path::new("-")) && solvers.len() > 1 {
+            bail!("stdin can only be streamed to one solver, select a part");
+        }
+    }
+    let visual = match &args.visualize {
+        Some(path) => Some((visual(&solvers, path, args.format)?, solvers[0], path)),
+        None => None,
+    };
+    let mut inputs = Inputs::new(selection);
+    if args.diagnose {
+        only_day(&solvers, 1, "--diagnose")?;
+        let input = inputs.get(solvers[0])?;
+        match &setting {
+            Some(Setting::Vocabulary(v)) => print!("{}", day1::diagnose_with(input, v)),
+            _ => print!("{}", day1::diagnose(input)),
+        }
+    }
+    let mut run_one = |solver: &Solver| {
+        let setting = setting.as_ref().filter(|s| s.applies_to(solver));
+        if args.stream {
+            let path = inputs.path(solver);
+            let input = &mut runner::open_input(&path)?;
+            match setting {
+                Some(s) => s.run_stream(input),
+                None => solver.run_stream(input),
+            }
+        } else {
+            let input = inputs.get(solver)?;
+            match setting {
+                Some(s) => s.run(input),
+                None => solver.run(input),
+            }
+        }
+    };
+    if args.format != Format::Text {
+        let rows = solvers
+            .into_iter()
+            .map(|solver| summary::Row {
+                solver,
+                result: run_one(solver).wrap_err_with(|| solver.to_string()),
+            })
+            .collect::<Vec<_>>();
+        if let Some((visual, solver, path)) = visual {
+            draw(visual, inputs.get(solver)?, path)?;
+        }
+        return print_records(args, &rows);
+    }
+    for solver in solvers {
+        let r = run_one(solver).wrap_err_with(|| solver.to_string())?;
+        match r.answer.kind() {
+            Kind::Grid => println!("{solver}:\n{}", r.answer),
+            _ => println!("{solver}: {}", r.answer),
+        }
+        println!("\tgenerator: {:?}, solver: {:?}", r.generator, r.solver);
+    }
+    if let Some((visual, solver, path)) = visual {
+        draw(visual, inputs.get(solver)?, path)?;
+    }
+    Ok(())
+}
+
+/// A setting from `--vocabulary` or `--bag`, passed to the solvers it applies to in place
+/// of their default.
+enum Setting {
+    Vocabulary(Vocabulary),
+    Bag(Bag),
+}
+
+impl Setting {
+    /// Day 1 part 2 counts the vocabulary's words, day 2 part 1 the games the bag allows.
+    fn applies_to(&self, solver: &Solver) -> bool {
+        match self {
+            Setting::Vocabulary(_) => solver.part == 2,
+            Setting::Bag(_) => solver.part == 1,
+        }
+    }
+
+    fn run(&self, input: &str) -> Result<Run> {
+        Run::time(|| match self {
+            Setting::Vocabulary(v) => day1::solve_part2_with(input, v),
+            Setting::Bag(b) => day2::solve_part1_with(input, b),
+        })
+    }
+
+    fn run_stream(&self, input: &mut dyn BufRead) -> Result<Run> {
+        Run::time(|| match self {
+            Setting::Vocabulary(v) => day1::stream_part2_with(input, v),
+            Setting::Bag(b) => day2::stream_part1_with(input, b),
+        })
+    }
+}
+
+/// Fails unless every solver is for 2023 Day `day`, the only one `flag` applies to.
+fn only_day(solvers: &[&Solver], day: u32, flag: &str) -> Result<()> {
+    if solvers.iter().any(|s| (s.year, s.day) != (2023, day)) {
+        bail!("`{flag}` only applies to 2023 Day {day}");
+    }
+    Ok(())
+}
+
+/// The visualisation of the selected day, checked before any solver runs.
+fn visual(solvers: &[&Solver], path: &Path, format: Format) -> Result<&'static Visual> {
+    render::Format::of(path)?;
+    if path == Path::new("-") && format != Format::Text {
+        bail!("`--visualize -` would mix the picture with the JSON output");
+    }
+    let solver = solvers[0];
+    runner::visual(solver.year, solver.day)
+        .ok_or_else(|| eyre!("{} Day {} has no visualisation", solver.year, solver.day))
+}
+
+fn draw(visual: &Visual, input: &str, path: &Path) -> Result<()> {
+    let picture = visual
+        .draw(input)
+        .wrap_err_with(|| format!("{} Day {}", visual.year, visual.day))?;
+    picture.write(path)?;
+    if path != Path::new("-") {
+        eprintln!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn run_all(args: &RunArgs, year: Option<u32>) -> Result<()> {
+    let solvers = args.selection.solvers(year)?;
+    let variant = args.selection.example.unwrap_or_default();
+    let rows = summary::run(
+        &solvers,
+        |solver| runner::read_input(&solver.default_input(variant)),
+        args.threads.unwrap_or_else(summary::default_threads),
+    );
+    if args.format != Format::Text {
+        return print_records(args, &rows);
+    }
+    print!("{}", summary::table(&rows));
+    any_failed(&rows)
+}
+
+/// Prints the rows as JSON, failing afterwards if any of them failed.
+fn print_records(args: &RunArgs, rows: &[summary::Row]) -> Result<()> {
+    let selection = &args.selection;
+    let variant = selection.example.unwrap_or_default();
+    let records = rows
+        .iter()
+        .map(|row| {
+            let input = selection
+                .input
+                .clone()
+                .unwrap_or_else(|| row.solver.default_input(variant));
+            Record::new(row, &input)
+        })
+        .collect::<Vec<_>>();
+    match args.format {
+        Format::Json => print!("{}", report::json(&records)),
+        _ => print!("{}", report::jsonl(&records)),
+    }
+    any_failed(rows)
+}
+
+fn any_failed(rows: &[summary::Row]) -> Result<()> {
+    let failed = summary::totals(rows).failed;
+    if failed > 0 {
+        bail!("{failed} of {} solvers failed", rows.len());
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs, year: Option<u32>) -> Result<()> {
+    let baseline = match &args.baseline {
+        Some(name) => Some(Baseline::load(&Baseline::path(name))?),
+        None => None,
+    };
+    let save = args.save.as_deref().map(Baseline::path);
+    let mut saved = match &save {
+        Some(path) => Baseline::load_or_default(path)?,
+        None => Baseline::default(),
+    };
+
+    let mut inputs = Inputs::new(&args.selection);
+    let mut regressions = 0;
+    for solver in args.selection.solvers(year)? {
+        let m = bench::measure(solver, inputs.get(solver)?, args.repeat)
+            .wrap_err_with(|| solver.to_string())?;
+        let changes = baseline.as_ref().and_then(|b| b.compare(&m));
+        println!("{solver}: {}", m.answer);
+        for (what, stats, change) in [
+            ("generator", m.generator, changes.map(|c| c.0)),
+            ("solver", m.solver, changes.map(|c| c.1)),
+        ] {
+            print!("\t{what}: {stats}");
+            if let Some(change) = change {
+                print!(" [{change}]");
+                if change.is_regression(args.threshold) {
+                    regressions += 1;
+                    print!(" REGRESSION");
+                }
+            }
+            println!();
+        }
+        saved.insert(&m);
+    }
+
+    if let Some(path) = &save {
+        saved.save(path)?;
+        println!("Saved baseline {}", path.display());
+    }
+    if regressions > 0 {
+        bail!(
+            "{regressions} regressions of more than {}% against baseline",
+            args.threshold
+        );
+    }
+    Ok(())
+}
+
+fn check(selection: &Selection, year: Option<u32>) -> Result<()> {
+    let solvers = selection.all_solvers(year)?;
+    let mut expected = vec![];
+    for year in runner::years() {
+        if solvers.iter().any(|s| s.year == year) {
+            expected.extend(answers::load(year)?);
+        }
+    }
+    let mut inputs = Inputs::new(selection);
+    let (mut checked, mut failed) = (0, 0);
+    for solver in solvers {
+        let wanted = selection
+            .input
+            .clone()
+            .or_else(|| selection.example.map(|v| solver.default_input(v)));
+        let listed = expected
+            .iter()
+            .filter(|e| e.applies_to(solver))
+            .filter(|e| wanted.as_ref().is_none_or(|i| *i == e.input))
+            .collect::<Vec<_>>();
+        if listed.is_empty() && solver.is_naive() && selection.example.is_none() {
+            continue;
+        }
+        if listed.is_empty() {
+            // Nothing to compare with, but it should at least run.
+            checked += 1;
+            match solver.run(inputs.get(solver)?) {
+                Ok(r) => println!("{solver}: {} (no known answer)", r.answer),
+                Err(e) => {
+                    failed += 1;
+                    println!("{solver}: FAILED: {e:#}");
+                }
+            }
+        }
+        for e in listed {
+            checked += 1;
+            let input = e.input.display();
+            match e.check(solver) {
+                Ok(()) => println!("{solver} [{input}]: ok"),
+                Err(err) => {
+                    failed += 1;
+                    println!("{solver} [{input}]: FAILED: {err:#}");
+                }
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} of {checked} checks failed");
+    }
+    Ok(())
+}
+
+fn fetch(year: u32, day: Option<u32>, session: &str, url: &str) -> Result<()> {
+    let store = Store::default();
+    let client = Client::new(url, session);
+    if let Some(day) = day {
+        let path = store.fetch(&client, year, day)?;
+        println!("Saved {}", path.display());
+        return Ok(());
+    }
+    let mut days = runner::select(Some(year), None, None, None)
+        .map(|s| s.day)
+        .collect::<Vec<_>>();
+    days.dedup();
+    for day in days {
+        if store.is_cached(year, day, Variant::Real) {
+            let path = store.path(year, day, Variant::Real);
+            println!("{} is already cached", path.display());
+        } else {
+            let path = store.fetch(&client, year, day)?;
+            println!("Saved {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn extract_examples(year: u32, page: &PathBuf, day: Option<u32>) -> Result<()> {
+    let html =
+        std::fs::read_to_string(page).wrap_err_with(|| format!("reading {}", page.display()))?;
+    let page = examples::parse(&html);
+    let Some(day) = day.or(page.day) else {
+        bail!("no day in the page, use --day");
+    };
+    let found = page.examples();
+    if found.is_empty() {
+        bail!("no examples in the page");
+    }
+    let store = Store::default();
+    let lines = examples::save(&found, &store, year, day)?;
+    for k in 1..=found.len() as u32 {
+        println!("{}", store.path(year, day, Variant::Example(k)).display());
+    }
+    for line in answers::append(year, &answers::path(year), &lines)? {
+        println!("Added answer: {line}");
+    }
+    Ok(())
+}
+
+fn new_day(year: u32, day: u32) -> Result<()> {
+    for path in scaffold::new_day(Path::new(""), &Store::default(), year, day)? {
+        println!("Created {}", path.display());
+    }
+    println!("Fill in the example, its answers in the tests, and fetch the input.");
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Some(spec) = &cli.trace {
+        spec.clone().in_year(cli.year).install()?;
+    }
+    match &cli.command {
+        Command::Run(args) => run(args, cli.year),
+        Command::Bench(args) => bench(args, cli.year),
+        Command::Check(selection) => check(selection, cli.year),
+        Command::Examples { page, day } => extract_examples(cli.year(), page, *day),
+        Command::NewDay { day } => new_day(cli.year(), *day),
+        Command::Fetch { day, session, url } => fetch(cli.year(), *day, session, url),
+    }
+}
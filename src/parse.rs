@@ -0,0 +1,150 @@
+//! Helpers for parsers that report where in the input they failed.
+
+use std::fmt::Display;
+
+use eyre::{eyre, Report, Result};
+
+/// Parses every line with `f`, naming the 1-based line number in errors, see [`on_line`].
+pub fn lines<T>(input: &str, mut f: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| f(l).map_err(|e| on_line(e, i + 1)))
+        .collect()
+}
+
+/// Names 1-based line `n` in `e`: `line 2, column 3: ...` for an error from [`error_at`],
+/// else `line 2: ...`.
+pub fn on_line(mut e: Report, n: usize) -> Report {
+    let columned = e
+        .chain()
+        .next()
+        .and_then(|e| e.downcast_ref::<At>())
+        .is_some_and(|at| at.line.is_none());
+    match e.downcast_mut::<At>() {
+        Some(at) if columned => {
+            at.line = Some(n);
+            e
+        }
+        _ => e.wrap_err(format!("line {n}")),
+    }
+}
+
+/// 1-based column at which `part` starts in `line`.
+///
+/// `part` must be a slice of `line`, e.g. obtained by splitting or trimming it.
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    debug_assert!(offset <= line.len(), "{part:?} is not a part of {line:?}");
+    offset + 1
+}
+
+/// An error about `part` of `line`, naming its column, and its line once it goes through
+/// [`on_line`].
+pub fn error_at(line: &str, part: &str, msg: impl Display) -> Report {
+    Report::new(At::new(None, column(line, part), msg, None))
+}
+
+/// [`error_at`] caused by `e`.
+pub fn wrap_at(e: Report, line: &str, part: &str, msg: impl Display) -> Report {
+    Report::new(At::new(None, column(line, part), msg, Some(e)))
+}
+
+/// An error at 1-based `line` and `column`, for parsers that count them, e.g. of grids.
+pub fn error_at_position(line: usize, column: usize, msg: impl Display) -> Report {
+    Report::new(At::new(Some(line), column, msg, None))
+}
+
+/// The place of an error in the input, and what went wrong there.
+#[derive(Debug)]
+struct At {
+    line: Option<usize>,
+    column: usize,
+    msg: String,
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl At {
+    fn new(line: Option<usize>, column: usize, msg: impl Display, source: Option<Report>) -> Self {
+        Self {
+            line,
+            column,
+            msg: msg.to_string(),
+            source: source.map(Into::into),
+        }
+    }
+}
+
+impl Display for At {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}, ")?;
+        }
+        write!(f, "column {}: {}", self.column, self.msg)
+    }
+}
+
+impl std::error::Error for At {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as _)
+    }
+}
+
+/// The error for arithmetic on input numbers whose result doesn't fit, for use with
+/// `checked_*` operations, e.g. `a.checked_add(b).ok_or_else(overflow)`.
+pub fn overflow() -> Report {
+    eyre!("overflow")
+}
+
+/// Asserts that `input` parses to a value that displays as `input` without its final
+/// newline, and that this text parses back to the same value.
+#[cfg(test)]
+pub(crate) fn assert_round_trip<T>(input: &str) -> Result<()>
+where
+    T: std::str::FromStr<Err = Report> + Display + PartialEq + std::fmt::Debug,
+{
+    let value = input.parse::<T>()?;
+    let shown = value.to_string();
+    assert_eq!(shown, input.strip_suffix('\n').unwrap_or(input));
+    assert_eq!(shown.parse::<T>()?, value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{error_at, lines, wrap_at};
+
+    #[test]
+    fn line_and_column() {
+        let e = lines("1 2\n3 x", |l| {
+            l.split(' ')
+                .map(|n| n.parse::<u32>().map_err(|e| error_at(l, n, e)))
+                .collect::<eyre::Result<Vec<_>>>()
+        })
+        .unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 3: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn causes() {
+        let e = lines("1 x", |l| {
+            let (_, n) = l.split_once(' ').unwrap();
+            n.parse::<u32>()
+                .map_err(|e| wrap_at(e.into(), l, n, "bad count"))
+        })
+        .unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 3: bad count: invalid digit found in string"
+        );
+        // A column under other context stays with its own message.
+        let e = lines("x", |l| {
+            Err::<(), _>(error_at(l, l, "bad").wrap_err("context"))
+        })
+        .unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: context: column 1: bad");
+    }
+}
@@ -0,0 +1,328 @@
+//! Pictures of what a solver did, drawn by `#[aoc_visual(dayN)]` functions: a grid of
+//! characters, each optionally highlighted in a colour, with a legend for the colours.
+//!
+//! [`Picture::write`] picks the format from the path: the terminal with ANSI colours for
+//! `-`, `.txt` or `.ans`, one coloured square per cell for `.ppm`, and the characters on
+//! coloured squares for `.svg`.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use eyre::{bail, Result, WrapErr};
+
+use crate::grid::{Coord, Grid};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const RED: Rgb = Rgb(0xe0, 0x40, 0x40);
+    pub const GREEN: Rgb = Rgb(0x40, 0xc0, 0x40);
+    pub const YELLOW: Rgb = Rgb(0xf0, 0xd0, 0x30);
+    pub const BLUE: Rgb = Rgb(0x40, 0x80, 0xe0);
+    pub const PURPLE: Rgb = Rgb(0xa0, 0x60, 0xd0);
+    /// Background of the image formats.
+    const BACKGROUND: Rgb = Rgb(0x10, 0x10, 0x10);
+    /// Uncoloured characters in the image formats.
+    const PLAIN: Rgb = Rgb(0x70, 0x70, 0x70);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub colour: Option<Rgb>,
+}
+
+/// Side of a cell in pixels, in PPM files.
+const CELL: usize = 4;
+/// Side of a cell in SVG files, big enough for its character.
+const SVG_CELL: usize = 12;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    cells: Grid<Cell>,
+    legend: Vec<(Rgb, String)>,
+}
+
+/// How [`Picture::write`] writes a picture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Ppm,
+    Svg,
+}
+
+impl Format {
+    /// The format for `path`, from its extension. `-` is the terminal.
+    pub fn of(path: &Path) -> Result<Self> {
+        if path == Path::new("-") {
+            return Ok(Format::Ansi);
+        }
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("txt" | "ans") => Ok(Format::Ansi),
+            Some("ppm") => Ok(Format::Ppm),
+            Some("svg") => Ok(Format::Svg),
+            _ => bail!(
+                "{}: unknown picture format, expected `.svg`, `.ppm`, `.txt` or `-`",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Picture {
+    /// The characters of `grid`, uncoloured.
+    pub fn new(grid: &Grid<char>) -> Self {
+        Self {
+            cells: grid.map(|&ch| Cell { ch, colour: None }),
+            legend: vec![],
+        }
+    }
+
+    /// Colours the cell at `p`, if it is in the picture.
+    pub fn paint<C: Coord>(&mut self, p: C, colour: Rgb) {
+        if let Some(cell) = self.cells.get_mut(p) {
+            cell.colour = Some(colour);
+        }
+    }
+
+    /// Explains what `colour` means, in the order the entries are added.
+    pub fn legend(&mut self, colour: Rgb, meaning: &str) {
+        self.legend.push((colour, meaning.to_string()));
+    }
+
+    pub fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    /// Writes the picture in the [`Format`] for `path`, to stdout for `-`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let data = match Format::of(path)? {
+            Format::Ansi => self.to_ansi().into_bytes(),
+            Format::Ppm => self.to_ppm(),
+            Format::Svg => self.to_svg().into_bytes(),
+        };
+        if path == Path::new("-") {
+            use std::io::Write;
+            std::io::stdout()
+                .write_all(&data)
+                .wrap_err("writing stdout")
+        } else {
+            std::fs::write(path, data).wrap_err_with(|| format!("writing {}", path.display()))
+        }
+    }
+
+    /// The characters with 24-bit ANSI colours, then the legend, one entry per line.
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.colour != current {
+                    match cell.colour {
+                        Some(c) => ansi(&mut out, c),
+                        None => out.push_str("\x1b[0m"),
+                    }
+                    current = cell.colour;
+                }
+                out.push(cell.ch);
+            }
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        for (colour, meaning) in &self.legend {
+            ansi(&mut out, *colour);
+            out.push_str("#\x1b[0m ");
+            out.push_str(meaning);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A binary PPM image with a square of [`CELL`] pixels per cell: its colour, or grey
+    /// for uncoloured characters other than `.` and space.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (w, h) = (self.cells.width() * CELL, self.cells.height() * CELL);
+        let mut out = format!("P6\n{w} {h}\n255\n").into_bytes();
+        for row in self.cells.rows() {
+            let pixels = row
+                .iter()
+                .flat_map(|cell| {
+                    let Rgb(r, g, b) = match cell.colour {
+                        Some(c) => c,
+                        None if matches!(cell.ch, '.' | ' ') => Rgb::BACKGROUND,
+                        None => Rgb::PLAIN,
+                    };
+                    [r, g, b].repeat(CELL)
+                })
+                .collect::<Vec<_>>();
+            for _ in 0..CELL {
+                out.extend(&pixels);
+            }
+        }
+        out
+    }
+
+    /// The characters in a monospace font, on a square of their colour if they have one,
+    /// with the legend below.
+    pub fn to_svg(&self) -> String {
+        let (w, h) = (self.cells.width(), self.cells.height());
+        let height = (h + self.legend.len()) * SVG_CELL;
+        let width = w.max(24) * SVG_CELL;
+        let mut out = String::new();
+        writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\">",
+            SVG_CELL - 2
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            Rgb::BACKGROUND.hex()
+        )
+        .unwrap();
+        for (p, cell) in self.cells.cells() {
+            let (x, y) = (p.x * SVG_CELL, p.y * SVG_CELL);
+            let text = match cell.colour {
+                Some(c) => {
+                    writeln!(
+                        out,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" \
+                         fill=\"{}\"/>",
+                        c.hex()
+                    )
+                    .unwrap();
+                    Rgb::BACKGROUND
+                }
+                None => Rgb::PLAIN,
+            };
+            if cell.ch != ' ' {
+                svg_text(&mut out, x + SVG_CELL / 2, y, text, &cell.ch.to_string());
+            }
+        }
+        for (i, (colour, meaning)) in self.legend.iter().enumerate() {
+            let y = (h + i) * SVG_CELL;
+            writeln!(
+                out,
+                "<rect x=\"0\" y=\"{y}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{}\"/>",
+                colour.hex()
+            )
+            .unwrap();
+            out.push_str("<g text-anchor=\"start\">");
+            svg_text(&mut out, SVG_CELL * 3 / 2, y, Rgb::PLAIN, meaning);
+            out.push_str("</g>\n");
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+impl Picture {
+    /// The characters, with coloured ones replaced by the letter `key` gives their colour,
+    /// for tests.
+    pub(crate) fn sketch(&self, key: &[(Rgb, char)]) -> String {
+        let letter = |cell: &Cell| match cell.colour {
+            Some(c) => key.iter().find(|k| k.0 == c).expect("colour in key").1,
+            None => cell.ch,
+        };
+        self.cells
+            .rows()
+            .map(|row| row.iter().map(letter).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Switches the foreground to `c`.
+fn ansi(out: &mut String, c: Rgb) {
+    write!(out, "\x1b[38;2;{};{};{}m", c.0, c.1, c.2).unwrap();
+}
+
+/// `text` in the cell row at `y`, centred on `x` unless anchored otherwise.
+fn svg_text(out: &mut String, x: usize, y: usize, colour: Rgb, text: &str) {
+    let text = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    writeln!(
+        out,
+        "<text x=\"{x}\" y=\"{}\" fill=\"{}\">{text}</text>",
+        y + SVG_CELL - 3,
+        colour.hex()
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Format, Picture, Rgb};
+    use crate::grid::{Grid, UPos};
+    use eyre::Result;
+    use std::path::Path;
+
+    fn picture() -> Result<Picture> {
+        let mut p = Picture::new(&"#.\n<#".parse::<Grid<char>>()?);
+        p.paint(UPos::new(0, 0), Rgb::RED);
+        p.paint(UPos::new(1, 1), Rgb::RED);
+        p.paint(UPos::new(5, 5), Rgb::RED);
+        p.legend(Rgb::RED, "walls");
+        Ok(p)
+    }
+
+    #[test]
+    fn ansi() -> Result<()> {
+        let red = "\x1b[38;2;224;64;64m";
+        let reset = "\x1b[0m";
+        assert_eq!(
+            picture()?.to_ansi(),
+            format!("{red}#{reset}.\n<{red}#{reset}\n{red}#{reset} walls\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ppm() -> Result<()> {
+        let ppm = picture()?.to_ppm();
+        let header = b"P6\n8 8\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 8 * 8 * 3);
+        assert_eq!(pixels[..3], [0xe0, 0x40, 0x40]);
+        // `.` is background, `<` plain.
+        assert_eq!(pixels[4 * 3..5 * 3], [0x10, 0x10, 0x10]);
+        assert_eq!(pixels[8 * 4 * 3..8 * 4 * 3 + 3], [0x70, 0x70, 0x70]);
+        Ok(())
+    }
+
+    #[test]
+    fn svg() -> Result<()> {
+        let svg = picture()?.to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("fill=\"#e04040\"").count(), 3);
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.contains(">walls</text>"));
+        Ok(())
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(Format::of(Path::new("-")).unwrap(), Format::Ansi);
+        assert_eq!(Format::of(Path::new("a/day10.svg")).unwrap(), Format::Svg);
+        assert_eq!(Format::of(Path::new("day10.ppm")).unwrap(), Format::Ppm);
+        let e = Format::of(Path::new("day10.png")).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day10.png: unknown picture format, expected `.svg`, `.ppm`, `.txt` or `-`"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Machine-readable results: `run --format json` prints an array of [`Record`]s and
+//! `--format jsonl` one record per line.
+//!
+//! The field names and their meaning are the schema; add fields rather than change them.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+pub use crate::answer::Kind;
+use crate::summary::{self, Row};
+
+/// The result of one solver on one input. Either `answer`, `kind` and the timings are
+/// set, or `error` is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// The name of an alternative solution.
+    pub name: Option<String>,
+    pub input: String,
+    /// As displayed, so a grid's rows are separated by newlines.
+    pub answer: Option<String>,
+    pub kind: Option<Kind>,
+    pub generator_ns: Option<u64>,
+    pub solver_ns: Option<u64>,
+    /// The error and its causes, outermost first.
+    pub error: Option<Vec<String>>,
+}
+
+impl Record {
+    pub fn new(row: &Row, input: &Path) -> Self {
+        let s = row.solver;
+        let mut record = Record {
+            year: s.year,
+            day: s.day,
+            part: s.part,
+            name: s.name.map(str::to_string),
+            input: input.display().to_string(),
+            answer: None,
+            kind: None,
+            generator_ns: None,
+            solver_ns: None,
+            error: None,
+        };
+        match &row.result {
+            Ok(r) => {
+                record.kind = Some(r.answer.kind());
+                record.answer = Some(r.answer.to_string());
+                record.generator_ns = Some(r.generator.as_nanos() as u64);
+                record.solver_ns = Some(r.solver.as_nanos() as u64);
+            }
+            Err(e) => record.error = Some(summary::causes(e)),
+        }
+        record
+    }
+}
+
+/// A JSON array, one record per line.
+pub fn json(records: &[Record]) -> String {
+    let lines = records
+        .iter()
+        .map(|r| format!("  {}", serde_json::to_string(r).unwrap()))
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        "[]\n".into()
+    } else {
+        format!("[\n{}\n]\n", lines.join(",\n"))
+    }
+}
+
+/// JSON Lines: one record per line.
+pub fn jsonl(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{json, jsonl, Record};
+    use crate::answer::Answer;
+    use crate::runner::{select, Run};
+    use crate::summary::Row;
+    use eyre::{eyre, Result};
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn records() -> Vec<Record> {
+        let solvers = select(Some(2023), Some(9), None, None).collect::<Vec<_>>();
+        let rows = [
+            Row {
+                solver: solvers[0],
+                result: Ok(Run {
+                    answer: Answer::from(-114),
+                    generator: Duration::from_nanos(1500),
+                    solver: Duration::from_micros(2),
+                }),
+            },
+            Row {
+                solver: solvers[1],
+                result: Err(eyre!("line 2: bad number")
+                    .wrap_err("generator failed")
+                    .wrap_err("2023 Day 9 - Part 2")),
+            },
+        ];
+        let input = Path::new("input/2023/day9.txt");
+        rows.iter().map(|r| Record::new(r, input)).collect()
+    }
+
+    #[test]
+    fn json_snapshot() -> Result<()> {
+        let records = records();
+        let s = json(&records);
+        assert_eq!(
+            s,
+            r#"[
+  {"year":2023,"day":9,"part":1,"name":null,"input":"input/2023/day9.txt","answer":"-114","kind":"signed","generator_ns":1500,"solver_ns":2000,"error":null},
+  {"year":2023,"day":9,"part":2,"name":null,"input":"input/2023/day9.txt","answer":null,"kind":null,"generator_ns":null,"solver_ns":null,"error":["generator failed","line 2: bad number"]}
+]
+"#
+        );
+        assert_eq!(serde_json::from_str::<Vec<Record>>(&s)?, records);
+        assert_eq!(json(&[]), "[]\n");
+        Ok(())
+    }
+
+    #[test]
+    fn jsonl_snapshot() -> Result<()> {
+        let records = records();
+        let s = jsonl(&records);
+        assert_eq!(
+            s,
+            r#"{"year":2023,"day":9,"part":1,"name":null,"input":"input/2023/day9.txt","answer":"-114","kind":"signed","generator_ns":1500,"solver_ns":2000,"error":null}
+{"year":2023,"day":9,"part":2,"name":null,"input":"input/2023/day9.txt","answer":null,"kind":null,"generator_ns":null,"solver_ns":null,"error":["generator failed","line 2: bad number"]}
+"#
+        );
+        let parsed = s
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Record>, _>>()?;
+        assert_eq!(parsed, records);
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+//! Registry of every `#[aoc]` solver and `#[aoc_visual]` drawing, discovered at build time,
+//! and helpers to run them.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use eyre::{Result, WrapErr};
+
+use crate::answer::Answer;
+use crate::inputs::{self, Store, Variant};
+use crate::render::Picture;
+
+/// Every registered solver, sorted by year, day, part and name.
+pub static SOLVERS: &[Solver] = include!(concat!(env!("OUT_DIR"), "/solvers.rs"));
+
+/// Every registered visualisation, sorted by year and day.
+pub static VISUALS: &[Visual] = include!(concat!(env!("OUT_DIR"), "/visuals.rs"));
+
+/// Years with at least one solver, in order.
+pub fn years() -> Vec<u32> {
+    let mut years = SOLVERS.iter().map(|s| s.year).collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+/// The year used when none is given.
+pub fn latest_year() -> u32 {
+    SOLVERS.last().map_or(2023, |s| s.year)
+}
+
+/// A generated input, ready to be solved any number of times.
+pub trait Solve {
+    fn solve(&self) -> Result<Answer>;
+}
+
+struct Prepared<I> {
+    input: I,
+    solve: fn(&I) -> Result<Answer>,
+}
+
+impl<I> Prepared<I> {
+    fn new(input: I, solve: fn(&I) -> Result<Answer>) -> Self {
+        Self { input, solve }
+    }
+}
+
+impl<I> Solve for Prepared<I> {
+    fn solve(&self) -> Result<Answer> {
+        (self.solve)(&self.input)
+    }
+}
+
+/// The name of brute-force reference solvers, see [`Solver::is_naive`].
+pub const NAIVE: &str = "naive";
+
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// Set for alternative solutions, e.g. `#[aoc(day1, part1, naive)]`.
+    pub name: Option<&'static str>,
+    prepare: for<'a> fn(&'a str) -> Result<Box<dyn Solve + 'a>>,
+    /// The `#[aoc_stream]` version, if any.
+    stream: Option<fn(&mut dyn BufRead) -> Result<Answer>>,
+}
+
+/// Answer and timings of a single run.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answer: Answer,
+    pub generator: Duration,
+    pub solver: Duration,
+}
+
+impl Run {
+    /// Runs `solve`, which reads and solves in one step, timed as the solver. For solvers
+    /// that take more than the input, e.g. [`crate::y2023::day1::solve_part2_with`].
+    pub fn time(solve: impl FnOnce() -> Result<Answer>) -> Result<Run> {
+        let start = Instant::now();
+        let answer = solve().wrap_err("solver failed")?;
+        Ok(Run {
+            answer,
+            generator: Duration::ZERO,
+            solver: start.elapsed(),
+        })
+    }
+}
+
+impl Solver {
+    /// Runs the generator for this solver, if any.
+    pub fn prepare<'a>(&self, input: &'a str) -> Result<Box<dyn Solve + 'a>> {
+        (self.prepare)(input).wrap_err("generator failed")
+    }
+
+    pub fn run(&self, input: &str) -> Result<Run> {
+        let start = Instant::now();
+        let prepared = self.prepare(input)?;
+        let generated = Instant::now();
+        let answer = prepared.solve().wrap_err("solver failed")?;
+        let solved = Instant::now();
+        Ok(Run {
+            answer,
+            generator: generated - start,
+            solver: solved - generated,
+        })
+    }
+
+    /// Whether there is a single-pass version, see [`crate::stream`].
+    pub fn can_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Runs the single-pass version. Reading and solving are one step, timed as the solver.
+    pub fn run_stream(&self, input: &mut dyn BufRead) -> Result<Run> {
+        let Some(stream) = self.stream else {
+            eyre::bail!("{self} has no streaming version");
+        };
+        Run::time(|| stream(input))
+    }
+
+    /// Naive solvers are brute-force references for cross-checking the others. They only
+    /// promise to finish on small inputs like the examples, and give up on bigger ones.
+    pub fn is_naive(&self) -> bool {
+        self.name == Some(NAIVE)
+    }
+
+    /// The cached input for this day, e.g. `input/<year>/day<N>.txt` for the real one.
+    pub fn default_input(&self, variant: Variant) -> PathBuf {
+        Store::default().path(self.year, self.day, variant)
+    }
+}
+
+impl std::fmt::Display for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} Day {} - Part {}", self.year, self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " - {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A picture of what a day's solvers do with an input, see [`crate::render`].
+pub struct Visual {
+    pub year: u32,
+    pub day: u32,
+    draw: fn(&str) -> Result<Picture>,
+}
+
+impl Visual {
+    pub fn draw(&self, input: &str) -> Result<Picture> {
+        (self.draw)(input).wrap_err("visualisation failed")
+    }
+}
+
+/// The visualisation of a day, if it has one.
+pub fn visual(year: u32, day: u32) -> Option<&'static Visual> {
+    VISUALS.iter().find(|v| (v.year, v.day) == (year, day))
+}
+
+/// Solvers matching the given filters. `None` matches anything.
+pub fn select(
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    name: Option<&str>,
+) -> impl Iterator<Item = &'static Solver> + '_ {
+    SOLVERS.iter().filter(move |s| {
+        year.is_none_or(|y| y == s.year)
+            && day.is_none_or(|d| d == s.day)
+            && part.is_none_or(|p| p == s.part)
+            && name.is_none_or(|n| Some(n) == s.name)
+    })
+}
+
+/// Reads an input file, or stdin for `-`, normalised by [`inputs::normalise`].
+pub fn read_input(path: &Path) -> Result<String> {
+    let mut s = String::new();
+    if path == Path::new("-") {
+        std::io::stdin()
+            .read_to_string(&mut s)
+            .wrap_err("reading stdin")?;
+    } else {
+        s = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {}", path.display()))?;
+    }
+    Ok(inputs::normalise(&s))
+}
+
+/// Opens an input file, or stdin for `-`, for [`Solver::run_stream`].
+pub fn open_input(path: &Path) -> Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    let file = std::fs::File::open(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{latest_year, select, years, SOLVERS, VISUALS};
+    use crate::answer::Answer;
+    use eyre::Result;
+    use itertools::Itertools;
+
+    #[test]
+    fn discovers_all_solvers() {
+        let found = SOLVERS
+            .iter()
+            .filter(|s| s.year == 2023 && s.name.is_none())
+            .map(|s| (s.day, s.part))
+            .collect_vec();
+        let expected = (1..=11).flat_map(|d| [(d, 1), (d, 2)]).collect_vec();
+        assert_eq!(found, expected);
+        let naive = SOLVERS
+            .iter()
+            .filter(|s| s.year == 2023 && s.is_naive())
+            .map(|s| (s.day, s.part))
+            .collect_vec();
+        assert_eq!(naive, [(5, 2), (6, 1), (6, 2), (8, 2), (11, 1), (11, 2)]);
+        let streams = SOLVERS
+            .iter()
+            .filter(|s| s.year == 2023 && s.can_stream())
+            .map(|s| (s.day, s.part))
+            .collect_vec();
+        let expected = [1, 2, 4, 6, 7, 9]
+            .into_iter()
+            .flat_map(|d| [(d, 1), (d, 2)]);
+        assert_eq!(streams, expected.collect_vec());
+        let visuals = VISUALS
+            .iter()
+            .filter(|v| v.year == 2023)
+            .map(|v| v.day)
+            .collect_vec();
+        assert_eq!(visuals, [3, 10, 11]);
+        assert!(years().contains(&2023));
+        assert_eq!(latest_year(), *years().last().unwrap());
+    }
+
+    #[test]
+    fn run_with_generator() -> Result<()> {
+        let input = include_str!("../input/2023/day9-ex1.txt");
+        let solver = select(Some(2023), Some(9), Some(2), None)
+            .exactly_one()
+            .ok()
+            .unwrap();
+        assert_eq!(solver.run(input)?.answer, Answer::Unsigned(2));
+        Ok(())
+    }
+
+    #[test]
+    fn run_without_generator() -> Result<()> {
+        let solver = select(Some(2023), Some(1), Some(1), None)
+            .exactly_one()
+            .ok()
+            .unwrap();
+        assert_eq!(
+            solver.run("1abc2\npqr3stu8vwx")?.answer,
+            Answer::Unsigned(50)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn run_stream() -> Result<()> {
+        let solver = select(Some(2023), Some(9), Some(1), None)
+            .exactly_one()
+            .ok()
+            .unwrap();
+        let mut input = include_str!("../input/2023/day9-ex1.txt").as_bytes();
+        assert_eq!(solver.run_stream(&mut input)?.answer, Answer::Unsigned(114));
+        let solver = select(Some(2023), Some(5), Some(1), None).next().unwrap();
+        let e = solver.run_stream(&mut "".as_bytes()).err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "2023 Day 5 - Part 1 has no streaming version"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generator_error() {
+        let solver = select(Some(2023), Some(4), Some(1), None)
+            .exactly_one()
+            .ok()
+            .unwrap();
+        let e = solver.run("Card x: 1 | 2").err().unwrap();
+        assert_eq!(e.to_string(), "generator failed");
+    }
+}
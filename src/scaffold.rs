@@ -0,0 +1,176 @@
+//! `new-day`: starts a day from `templates/day.rs.in`, with the module registered in
+//! `src/y<year>/mod.rs`, empty input files and failing placeholder tests.
+
+use std::path::{Path, PathBuf};
+
+use eyre::{bail, Result, WrapErr};
+
+use crate::inputs::{Store, Variant};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.in");
+
+/// The source of a new day module.
+pub fn module(year: u32, day: u32) -> String {
+    TEMPLATE
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+}
+
+/// Adds `pub mod <name>;` to the `pub mod` lines of a crate root, keeping them sorted.
+pub fn register(lib: &str, name: &str) -> Result<String> {
+    let line = format!("pub mod {name};");
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    if lines.iter().any(|l| l.trim() == line) {
+        bail!("{name} is already registered");
+    }
+    // Sorted by name, as rustfmt does.
+    let mods = (0..lines.len())
+        .filter_map(|i| {
+            let name = lines[i].strip_prefix("pub mod ")?.strip_suffix(';')?;
+            Some((i, name))
+        })
+        .collect::<Vec<_>>();
+    let at = mods
+        .iter()
+        .find(|&&(_, m)| m > name)
+        .map(|&(i, _)| i)
+        .or_else(|| mods.last().map(|&(i, _)| i + 1))
+        .unwrap_or(lines.len());
+    lines.insert(at, &line);
+    let mut out = lines.join("\n");
+    if lib.ends_with('\n') || lib.is_empty() {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Creates `src/y<year>/day<N>.rs` under `root` and registers it in `src/y<year>/mod.rs`,
+/// starting that module (registered in `src/lib.rs`) for a new year. Then creates empty
+/// placeholders for the real input and the first example. Returns the new files.
+pub fn new_day(root: &Path, store: &Store, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let dir = src.join(format!("y{year}"));
+    let path = dir.join(format!("day{day}.rs"));
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    let mod_path = dir.join("mod.rs");
+    let new_year = !mod_path.exists();
+    let (year_mod, lib) = if new_year {
+        let lib_path = src.join("lib.rs");
+        let lib = read(&lib_path)?;
+        let lib = register(&lib, &format!("y{year}"))?;
+        (
+            format!("//! Advent of Code {year}.\n\n"),
+            Some((lib_path, lib)),
+        )
+    } else {
+        (read(&mod_path)?, None)
+    };
+    let year_mod = register(&year_mod, &format!("day{day}"))?;
+
+    std::fs::create_dir_all(&dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+    write(&path, &module(year, day))?;
+    write(&mod_path, &year_mod)?;
+    if let Some((lib_path, lib)) = lib {
+        write(&lib_path, &lib)?;
+    }
+
+    let mut created = vec![path];
+    if new_year {
+        created.push(mod_path);
+    }
+    for variant in [Variant::Real, Variant::Example(1)] {
+        if store.placeholder(year, day, variant)? {
+            created.push(store.path(year, day, variant));
+        }
+    }
+    Ok(created)
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    std::fs::write(path, contents).wrap_err_with(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{new_day, register};
+    use crate::inputs::{Store, Variant};
+    use eyre::Result;
+
+    #[test]
+    fn registers_in_order() -> Result<()> {
+        let lib = "pub mod day1;\npub mod day10;\npub mod day2;\npub mod grid;\n\nfoo! {}\n";
+        assert_eq!(
+            register(lib, "day12")?,
+            "pub mod day1;\npub mod day10;\npub mod day12;\npub mod day2;\npub mod grid;\n\nfoo! {}\n"
+        );
+        assert_eq!(register("pub mod a;", "b")?, "pub mod a;\npub mod b;");
+        assert_eq!(register("", "day1")?, "pub mod day1;\n");
+        let e = register(lib, "day2").unwrap_err();
+        assert_eq!(e.to_string(), "day2 is already registered");
+        Ok(())
+    }
+
+    #[test]
+    fn module_from_template() {
+        let m = super::module(2023, 12);
+        assert!(m.contains("#[aoc_generator(day12)]"));
+        assert!(m.contains("#[aoc(day12, part2)]"));
+        assert!(m.contains("pub fn solve_part2(input: &str) -> Result<Answer>"));
+        assert!(m.contains("include_str!(\"../../input/2023/day12-ex1.txt\")"));
+        assert!(!m.contains("{{"));
+    }
+
+    #[test]
+    fn creates_day() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/y2023"))?;
+        std::fs::write(root.join("src/lib.rs"), "pub mod runner;\npub mod y2023;\n")?;
+        std::fs::write(root.join("src/y2023/mod.rs"), "pub mod day1;\n")?;
+        let store = Store::new(root.join("input"));
+        store.save(2023, 2, Variant::Real, "real input")?;
+
+        let created = new_day(&root, &store, 2023, 2)?;
+        assert_eq!(
+            created,
+            [
+                root.join("src/y2023/day2.rs"),
+                root.join("input/2023/day2-ex1.txt")
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/y2023/mod.rs"))?,
+            "pub mod day1;\npub mod day2;\n"
+        );
+        assert_eq!(store.read(2023, 2, Variant::Real)?, "real input");
+        assert_eq!(store.read(2023, 2, Variant::Example(1))?, "");
+
+        let e = new_day(&root, &store, 2023, 2).unwrap_err();
+        assert!(e.to_string().ends_with("day2.rs already exists"));
+
+        // The first day of a year starts its module.
+        let created = new_day(&root, &store, 2024, 1)?;
+        assert_eq!(
+            created[..2],
+            [
+                root.join("src/y2024/day1.rs"),
+                root.join("src/y2024/mod.rs")
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/y2024/mod.rs"))?,
+            "//! Advent of Code 2024.\n\npub mod day1;\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs"))?,
+            "pub mod runner;\npub mod y2023;\npub mod y2024;\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Helpers for `#[aoc_stream]` solvers, which solve in a single pass over a [`BufRead`] so
+//! that inputs too big to hold in memory can be solved, e.g. for stress tests.
+//!
+//! Input is read as [`normalise`](crate::inputs::normalise) would leave it: lines may end in
+//! `\r\n`, and trailing empty lines are ignored.
+
+use std::io::BufRead;
+
+use eyre::{bail, Result, WrapErr};
+
+use crate::parse::on_line;
+
+/// Calls `f` on every line, reusing one buffer, and names the 1-based line number in
+/// errors. Only one line is held at a time.
+pub fn lines(input: &mut dyn BufRead, mut f: impl FnMut(&str) -> Result<()>) -> Result<()> {
+    let mut buf = String::new();
+    let mut n = 0;
+    // Empty lines not passed on yet, as they are dropped at the end of the input.
+    let mut empty = 0;
+    loop {
+        buf.clear();
+        let read = input
+            .read_line(&mut buf)
+            .wrap_err_with(|| format!("line {}", n + 1))?;
+        if read == 0 {
+            return Ok(());
+        }
+        n += 1;
+        let line = buf.strip_suffix('\n').unwrap_or(&buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            empty += 1;
+            continue;
+        }
+        for i in n - empty..n {
+            f("").map_err(|e| on_line(e, i))?;
+        }
+        empty = 0;
+        f(line).map_err(|e| on_line(e, n))?;
+    }
+}
+
+/// Words longer than this are an error in [`words`].
+pub const MAX_WORD: usize = 64;
+
+/// Calls `f` on each whitespace-separated word of the next line. Only the current word is
+/// held, so lines may be arbitrarily long. Returns whether there was a line.
+pub fn words(input: &mut dyn BufRead, mut f: impl FnMut(&str) -> Result<()>) -> Result<bool> {
+    let mut word = Vec::with_capacity(MAX_WORD);
+    let mut emit = |word: &mut Vec<u8>| -> Result<()> {
+        if !word.is_empty() {
+            f(std::str::from_utf8(word)?)?;
+            word.clear();
+        }
+        Ok(())
+    };
+    let mut any = false;
+    loop {
+        let buf = input.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        any = true;
+        let end = buf.iter().position(|&b| b == b'\n');
+        let used = end.map_or(buf.len(), |i| i + 1);
+        for &b in &buf[..end.unwrap_or(buf.len())] {
+            if b.is_ascii_whitespace() {
+                emit(&mut word)?;
+            } else if word.len() == MAX_WORD {
+                bail!("word longer than {MAX_WORD} bytes");
+            } else {
+                word.push(b);
+            }
+        }
+        input.consume(used);
+        if end.is_some() {
+            break;
+        }
+    }
+    emit(&mut word)?;
+    Ok(any)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lines, words};
+    use eyre::Result;
+
+    fn collect(input: &str) -> Result<Vec<String>> {
+        let mut out = vec![];
+        lines(&mut input.as_bytes(), |l| {
+            out.push(l.to_string());
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    #[test]
+    fn lines_like_normalise() -> Result<()> {
+        assert_eq!(collect("a\r\nb\n\n\nc\n\n")?, ["a", "b", "", "", "c"]);
+        assert_eq!(collect("a\r")?, ["a"]);
+        assert!(collect("\n\n")?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn line_numbers() {
+        let e = lines(&mut "a\n\nb".as_bytes(), |l| match l {
+            "" => Err(eyre::eyre!("empty")),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: empty");
+        let e = lines(&mut &b"a\n\xff"[..], |_| Ok(())).unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2: stream did not contain valid UTF-8"
+        );
+    }
+
+    #[test]
+    fn words_of_lines() -> Result<()> {
+        let mut input = "Time:  7 15\r\nDistance: 9\n".as_bytes();
+        let mut found = vec![];
+        while words(&mut input, |w| {
+            found.push(w.to_string());
+            Ok(())
+        })? {
+            found.push("/".into());
+        }
+        assert_eq!(found, ["Time:", "7", "15", "/", "Distance:", "9", "/"]);
+
+        let long = "1".repeat(super::MAX_WORD + 1);
+        let e = words(&mut long.as_bytes(), |_| Ok(())).unwrap_err();
+        assert_eq!(e.to_string(), "word longer than 64 bytes");
+        Ok(())
+    }
+}
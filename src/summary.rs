@@ -0,0 +1,222 @@
+//! `run --all`: every selected solver on its own input, spread over threads, summarised
+//! in one table. A failing solver gets its error in the table instead of stopping the rest.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use eyre::{Result, WrapErr};
+
+use crate::runner::{Run, Solver};
+
+/// The outcome of one solver.
+pub struct Row {
+    pub solver: &'static Solver,
+    pub result: Result<Run>,
+}
+
+/// Runs each solver on `input(solver)` using up to `threads` threads, and returns the
+/// outcomes in the order of `solvers`.
+pub fn run<F>(solvers: &[&'static Solver], input: F, threads: usize) -> Vec<Row>
+where
+    F: Fn(&Solver) -> Result<String> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..solvers.len()).map(|_| None).collect::<Vec<_>>());
+    std::thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, solvers.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&solver) = solvers.get(i) else {
+                    break;
+                };
+                let result = input(solver).and_then(|input| solver.run(&input));
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    solvers
+        .iter()
+        .zip(results.into_inner().unwrap())
+        .map(|(&solver, result)| Row {
+            solver,
+            result: result
+                .unwrap_or_else(|| Err(eyre::eyre!("not run")))
+                .wrap_err_with(|| solver.to_string()),
+        })
+        .collect()
+}
+
+/// The messages of a row's error, outermost first, without the solver it was wrapped in.
+pub fn causes(e: &eyre::Report) -> Vec<String> {
+    e.chain().skip(1).map(ToString::to_string).collect()
+}
+
+/// Number of threads to use when none is given.
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Totals of the successful rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub generator: Duration,
+    pub solver: Duration,
+    pub failed: usize,
+}
+
+pub fn totals(rows: &[Row]) -> Totals {
+    let mut totals = Totals::default();
+    for row in rows {
+        match &row.result {
+            Ok(r) => {
+                totals.generator += r.generator;
+                totals.solver += r.solver;
+            }
+            Err(_) => totals.failed += 1,
+        }
+    }
+    totals
+}
+
+/// The rows as an aligned table with a total line. Failures show their error chain in
+/// place of the answer.
+pub fn table(rows: &[Row]) -> String {
+    let mut cells =
+        vec![["Year", "Day", "Part", "Answer", "Generator", "Solver"].map(String::from)];
+    for row in rows {
+        let s = row.solver;
+        let part = match s.name {
+            Some(name) => format!("{} {name}", s.part),
+            None => s.part.to_string(),
+        };
+        let (answer, generator, solver) = match &row.result {
+            Ok(r) => (
+                r.answer.to_line(),
+                format!("{:?}", r.generator),
+                format!("{:?}", r.solver),
+            ),
+            Err(e) => (
+                format!("FAILED: {}", causes(e).join(": ")),
+                "-".into(),
+                "-".into(),
+            ),
+        };
+        cells.push([
+            s.year.to_string(),
+            s.day.to_string(),
+            part,
+            answer,
+            generator,
+            solver,
+        ]);
+    }
+    let totals = totals(rows);
+    let answer = match totals.failed {
+        0 => String::new(),
+        n => format!("{n} failed"),
+    };
+    cells.push([
+        "Total".into(),
+        String::new(),
+        String::new(),
+        answer,
+        format!("{:?}", totals.generator),
+        format!("{:?}", totals.solver),
+    ]);
+
+    let widths = (0..6)
+        .map(|c| {
+            cells
+                .iter()
+                .map(|r| r[c].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for row in &cells {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run, table, totals, Row};
+    use crate::answer::Answer;
+    use crate::runner::{select, Run};
+    use eyre::{bail, eyre};
+    use std::time::Duration;
+
+    #[test]
+    fn runs_in_order_despite_failures() {
+        let solvers = select(Some(2023), Some(1), None, None)
+            .chain(select(Some(2023), Some(4), None, None))
+            .chain(select(Some(2023), Some(9), Some(1), None))
+            .collect::<Vec<_>>();
+        let rows = run(
+            &solvers,
+            |s| match s.day {
+                1 => Ok(include_str!("../input/2023/day1-ex1.txt").into()),
+                4 => Ok("Card x: 1 | 2".into()),
+                _ => bail!("no input"),
+            },
+            3,
+        );
+        assert!(rows
+            .iter()
+            .zip(&solvers)
+            .all(|(r, s)| std::ptr::eq(r.solver, *s)));
+        assert_eq!(
+            rows[0].result.as_ref().unwrap().answer,
+            Answer::Unsigned(142)
+        );
+        let errors = rows
+            .iter()
+            .map(|r| r.result.as_ref().err().map(|e| e.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(errors[0], None);
+        assert_eq!(errors[2].as_deref(), Some("2023 Day 4 - Part 1"));
+        assert_eq!(
+            format!("{:#}", rows[4].result.as_ref().unwrap_err()),
+            "2023 Day 9 - Part 1: no input"
+        );
+        assert_eq!(totals(&rows).failed, 3);
+    }
+
+    #[test]
+    fn formats_table() {
+        let solvers = select(Some(2023), Some(1), None, None).collect::<Vec<_>>();
+        let rows = vec![
+            Row {
+                solver: solvers[0],
+                result: Ok(Run {
+                    answer: Answer::Unsigned(54388),
+                    generator: Duration::ZERO,
+                    solver: Duration::from_micros(12),
+                }),
+            },
+            Row {
+                solver: solvers[1],
+                result: Err(eyre!("bad line").wrap_err("2023 Day 1 - Part 2")),
+            },
+        ];
+        assert_eq!(
+            table(&rows),
+            "\
+Year   Day  Part  Answer            Generator  Solver
+2023   1    1     54388             0ns        12µs
+2023   1    2     FAILED: bad line  -          -
+Total             1 failed          0ns        12µs
+"
+        );
+    }
+}
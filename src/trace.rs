@@ -0,0 +1,186 @@
+//! Step-by-step traces of solvers, for `--trace`. Solvers emit [`tracing`] events: `debug`
+//! for each step of an algorithm, e.g. every step of day 10's loop walk, and `trace` for
+//! the finest detail, e.g. every pipe traversed. A [`Spec`] picks the days to show.
+
+use std::str::FromStr;
+
+use eyre::{bail, eyre, Result};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+/// Days to trace, and the most detailed level to show for each, e.g. `day10,day11=debug`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spec {
+    year: Option<u32>,
+    /// `None` for every day.
+    days: Vec<(Option<u32>, Level)>,
+}
+
+impl FromStr for Spec {
+    type Err = eyre::Report;
+
+    /// Comma-separated `dayN` or `all`, each optionally with `=<level>`. The level defaults
+    /// to `trace`, which shows everything.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let days = s.split(',').map(directive).collect::<Result<_>>()?;
+        Ok(Self { year: None, days })
+    }
+}
+
+/// `dayN` or `all`, optionally with `=<level>`.
+fn directive(s: &str) -> Result<(Option<u32>, Level)> {
+    let (day, level) = match s.split_once('=') {
+        Some((day, level)) => {
+            let level = level.parse().map_err(|_| {
+                eyre!("bad level `{level}`, expected `trace`, `debug`, `info`, `warn` or `error`")
+            })?;
+            (day, level)
+        }
+        None => (s, Level::TRACE),
+    };
+    if day == "all" {
+        return Ok((None, level));
+    }
+    let n = day
+        .strip_prefix("day")
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| eyre!("bad day `{day}`, expected `dayN` or `all`"))?;
+    Ok((Some(n), level))
+}
+
+impl Spec {
+    /// Only traces `year`, if given.
+    pub fn in_year(self, year: Option<u32>) -> Self {
+        Self { year, ..self }
+    }
+
+    /// Whether to show an event at `level` from the module `target`, e.g.
+    /// `aoc_2023::y2023::day7part2`.
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let Some((year, day)) = day_of(target) else {
+            return false;
+        };
+        self.year.is_none_or(|y| y == year)
+            && self
+                .days
+                .iter()
+                .any(|&(d, max)| d.is_none_or(|d| d == day) && level <= max)
+    }
+
+    /// Shows the selected events on stderr from now on. Fails if something else already
+    /// collects events.
+    pub fn install(self) -> Result<()> {
+        if tracing::subscriber::set_global_default(self.subscriber(std::io::stderr)).is_err() {
+            bail!("tracing is already set up");
+        }
+        Ok(())
+    }
+
+    /// Writes the selected events to `writer`, one per line.
+    fn subscriber<W>(self, writer: W) -> impl Subscriber + Send + Sync
+    where
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let layer = tracing_subscriber::fmt::layer()
+            .without_time()
+            .with_ansi(false)
+            .with_writer(writer)
+            .with_filter(filter_fn(move |meta| {
+                self.enabled(meta.target(), *meta.level())
+            }));
+        tracing_subscriber::registry().with(layer)
+    }
+}
+
+/// The year and day of a day's module path, e.g. 2023 and 7 for
+/// `aoc_2023::y2023::day7part2`.
+fn day_of(target: &str) -> Option<(u32, u32)> {
+    let mut parts = target.split("::").skip(1);
+    let year = parts.next()?.strip_prefix('y')?.parse().ok()?;
+    let day = parts.next()?.strip_prefix("day")?;
+    let digits = day.find(|c: char| !c.is_ascii_digit()).unwrap_or(day.len());
+    Some((year, day[..digits].parse().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Spec;
+    use eyre::Result;
+    use tracing::Level;
+
+    const DAY1: &str = "aoc_2023::y2023::day1";
+    const DAY10: &str = "aoc_2023::y2023::day10";
+
+    #[test]
+    fn days() -> Result<()> {
+        let spec = "day1".parse::<Spec>()?;
+        assert!(spec.enabled(DAY1, Level::TRACE));
+        assert!(!spec.enabled(DAY10, Level::TRACE));
+        assert!(!spec.enabled("aoc_2023::runner", Level::ERROR));
+
+        let spec = "day10=debug,day7".parse::<Spec>()?;
+        assert!(spec.enabled(DAY10, Level::DEBUG));
+        assert!(spec.enabled(DAY10, Level::INFO));
+        assert!(!spec.enabled(DAY10, Level::TRACE));
+        assert!(spec.enabled("aoc_2023::y2023::day7part2", Level::TRACE));
+
+        let spec = "all=info".parse::<Spec>()?.in_year(Some(2024));
+        assert!(!spec.enabled(DAY1, Level::INFO));
+        assert!(spec.enabled("aoc_2023::y2024::day1", Level::INFO));
+        Ok(())
+    }
+
+    #[test]
+    fn events() -> Result<()> {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = "day10=debug"
+            .parse::<Spec>()?
+            .subscriber(move || writer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            crate::y2023::day10::solve_part1(".....\n.S-7.\n.|.|.\n.L-J.\n.....")?;
+            crate::y2023::day11::solve_part1("#.\n.#")
+        })?;
+        let out = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[..2],
+            [
+                "DEBUG aoc_2023::y2023::day10: start x=1 y=1 tile=F",
+                "DEBUG aoc_2023::y2023::day10: walk step=1 end=0 x=1 y=2 tile=|",
+            ]
+        );
+        // Four steps at both ends, and nothing from day 11 or at trace level.
+        assert_eq!(lines.len(), 1 + 8);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_spec() {
+        let e = "10".parse::<Spec>().unwrap_err();
+        assert_eq!(e.to_string(), "bad day `10`, expected `dayN` or `all`");
+        let e = "day10=loud".parse::<Spec>().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "bad level `loud`, expected `trace`, `debug`, `info`, `warn` or `error`"
+        );
+    }
+}
@@ -0,0 +1,762 @@
+//! Day 1: Trebuchet?! The calibration value of a line is its first and last digit.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{error_at, on_line, overflow};
+use crate::stream;
+use aoc_macros::{aoc, aoc_stream};
+use eyre::{bail, eyre, Result, WrapErr};
+use itertools::Itertools;
+
+#[aoc(day1, part1)]
+pub(crate) fn part1(input: &str) -> Result<u32> {
+    let mut sum: u32 = 0;
+    for (i, l) in input.lines().enumerate() {
+        let nums = l.chars().filter_map(|c| c.to_digit(10)).collect_vec();
+        let value = calibration_value(&nums).wrap_err_with(|| format!("line {}", i + 1))?;
+        sum = sum.checked_add(value).ok_or_else(overflow)?;
+    }
+    Ok(sum)
+}
+
+#[aoc_stream(day1, part1)]
+pub(crate) fn part1_stream(input: &mut dyn BufRead) -> Result<u32> {
+    sum_lines(input, |l| {
+        calibration_value(&l.chars().filter_map(|c| c.to_digit(10)).collect_vec())
+    })
+}
+
+/// The sum of the calibration values `value` gives each line.
+fn sum_lines(input: &mut dyn BufRead, value: impl Fn(&str) -> Result<u32>) -> Result<u32> {
+    let mut sum: u32 = 0;
+    stream::lines(input, |l| {
+        sum = sum.checked_add(value(l)?).ok_or_else(overflow)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+fn calibration_value(nums: &[u32]) -> Result<u32> {
+    match (nums.first(), nums.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(eyre!("no digit")),
+    }
+}
+
+/// The digits, which count in part 2 whatever the [`Vocabulary`].
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const SWEDISH: [(&str, u32); 10] = [
+    ("noll", 0),
+    ("ett", 1),
+    ("två", 2),
+    ("tre", 3),
+    ("fyra", 4),
+    ("fem", 5),
+    ("sex", 6),
+    ("sju", 7),
+    ("åtta", 8),
+    ("nio", 9),
+];
+
+const GERMAN: [(&str, u32); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const ROMAN: [(&str, u32); 9] = [
+    ("I", 1),
+    ("II", 2),
+    ("III", 3),
+    ("IV", 4),
+    ("V", 5),
+    ("VI", 6),
+    ("VII", 7),
+    ("VIII", 8),
+    ("IX", 9),
+];
+
+/// The words part 2 finds besides the digits, with the digit each stands for. Words are
+/// UTF-8 and may overlap, and where several start in the same place the longest counts,
+/// e.g. `VIII` rather than `V` in Roman numerals.
+///
+/// Besides the built-in vocabularies, [`Vocabulary::NAMES`], one parses from lines of a
+/// word and its digit, e.g. `två 2`, with blank lines and `#` comments ignored.
+///
+/// ```
+/// use aoc_2023::answer::Answer;
+/// use aoc_2023::y2023::day1::{self, Vocabulary};
+///
+/// let swedish = Vocabulary::named("swedish").unwrap();
+/// assert_eq!(day1::solve_part2_with("tvåtttreåtta", &swedish)?, Answer::Unsigned(28));
+/// let mine: Vocabulary = "# Vowels\na 1\ne 2".parse()?;
+/// assert_eq!(day1::solve_part2_with("baked", &mine)?, Answer::Unsigned(12));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub const NAMES: [&str; 4] = ["english", "swedish", "german", "roman"];
+
+    /// The numbers spelled out in English, as in the puzzle.
+    pub fn english() -> Self {
+        Self::from_table(&ENGLISH)
+    }
+
+    /// One of [`Vocabulary::NAMES`].
+    pub fn named(name: &str) -> Option<Self> {
+        let table: &[_] = match name {
+            "english" => &ENGLISH,
+            "swedish" => &SWEDISH,
+            "german" => &GERMAN,
+            "roman" => &ROMAN,
+            _ => return None,
+        };
+        Some(Self::from_table(table))
+    }
+
+    /// One of [`Vocabulary::NAMES`], or else the file at `name`.
+    pub fn load(name: &str) -> Result<Self> {
+        if let Some(v) = Self::named(name) {
+            return Ok(v);
+        }
+        let text = std::fs::read_to_string(name).wrap_err_with(|| {
+            let names = Self::NAMES.join(", ");
+            format!("reading {name}, which isn't one of the vocabularies {names}")
+        })?;
+        text.parse().wrap_err_with(|| format!("parsing {name}"))
+    }
+
+    fn from_table(table: &[(&str, u32)]) -> Self {
+        let words = table.iter().map(|&(w, n)| (w.to_string(), n)).collect();
+        Self { words }
+    }
+
+    /// The words with the digits they stand for, in the order given.
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+}
+
+impl std::str::FromStr for Vocabulary {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = vec![];
+        for (i, l) in s.lines().enumerate() {
+            let l = l.trim();
+            if l.is_empty() || l.starts_with('#') {
+                continue;
+            }
+            words.push(word(l).map_err(|e| on_line(e, i + 1))?);
+        }
+        if words.is_empty() {
+            bail!("no words");
+        }
+        Ok(Self { words })
+    }
+}
+
+/// A word and its digit, e.g. `två 2`.
+fn word(l: &str) -> Result<(String, u32)> {
+    let Some((word, n)) = l.split_whitespace().collect_tuple() else {
+        bail!("expected a word and its digit, e.g. `two 2`");
+    };
+    match n.parse() {
+        Ok(n) if n <= 9 => Ok((word.to_string(), n)),
+        _ => Err(error_at(l, n, format!("`{n}` is not a digit"))),
+    }
+}
+
+/// The scanner for English, built once.
+fn english() -> &'static Scanner {
+    static ENGLISH: OnceLock<Scanner> = OnceLock::new();
+    ENGLISH.get_or_init(|| Scanner::from(&Vocabulary::english()))
+}
+
+/// Every digit in `line`, in order of where they start, by finding every match of every
+/// word and sorting them. Overlapping words all count, e.g. 8 and 2 in `eightwo`.
+#[cfg(test)]
+fn nums_in_line(line: &str) -> Vec<u32> {
+    let mut v = vec![];
+    for digit in DIGITS.iter().chain(&ENGLISH) {
+        v.extend(line.match_indices(digit.0).map(|(p, _)| (p, digit.1)));
+    }
+    v.sort_by_key(|&(p, _)| p);
+    v.iter().map(|&(_, n)| n).collect()
+}
+
+/// Finds the first and last of a table of words in a line, without allocating, by running
+/// an Aho–Corasick automaton forwards for the first and one of the reversed words
+/// backwards for the last. Overlapping words all count, so `eightwo` starts with 8 and
+/// ends with 2.
+struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+    /// The length of the longest word.
+    longest: usize,
+}
+
+impl From<&Vocabulary> for Scanner {
+    /// The digits and the words of `vocabulary`.
+    fn from(vocabulary: &Vocabulary) -> Self {
+        let words = vocabulary.words.iter().map(|(w, n)| (w.as_str(), *n));
+        Scanner::new(&DIGITS.into_iter().chain(words).collect_vec())
+    }
+}
+
+impl Scanner {
+    fn new(words: &[(&str, u32)]) -> Self {
+        let reversed = words
+            .iter()
+            .map(|&(w, n)| (w.bytes().rev().collect(), n))
+            .collect_vec();
+        let forward = words.iter().map(|&(w, n)| (w.as_bytes().to_vec(), n));
+        Self {
+            forward: Automaton::new(&forward.collect_vec()),
+            backward: Automaton::new(&reversed),
+            longest: words.iter().map(|(w, _)| w.len()).max().unwrap_or(0),
+        }
+    }
+
+    /// The sum of the calibration values of the lines of `input`.
+    fn sum(&self, input: &str) -> Result<u32> {
+        let mut sum: u32 = 0;
+        for (i, line) in input.lines().enumerate() {
+            let value = self
+                .value(line)
+                .wrap_err_with(|| format!("line {}", i + 1))?;
+            sum = sum.checked_add(value).ok_or_else(overflow)?;
+        }
+        Ok(sum)
+    }
+
+    /// The calibration value of `line` from its first and last word.
+    fn value(&self, line: &str) -> Result<u32> {
+        let line = line.as_bytes();
+        match (self.first(line), self.last(line)) {
+            (Some((_, first)), Some((_, last))) => Ok(first * 10 + last),
+            _ => Err(eyre!("no digit")),
+        }
+    }
+
+    /// The word starting first, the longest one if several start there.
+    fn first(&self, line: &[u8]) -> Option<(Range<usize>, u32)> {
+        let mut state = 0;
+        let mut best: Option<(Range<usize>, u32)> = None;
+        for (i, &b) in line.iter().enumerate() {
+            // Words starting at or before the best one have ended by now.
+            if best
+                .as_ref()
+                .is_some_and(|(span, _)| i >= span.start + self.longest)
+            {
+                break;
+            }
+            state = self.forward.step(state, b);
+            if let Some((len, n)) = self.forward.found[state] {
+                let start = i + 1 - len;
+                if best.as_ref().is_none_or(|(span, _)| start <= span.start) {
+                    best = Some((start..i + 1, n));
+                }
+            }
+        }
+        best
+    }
+
+    /// The word starting last, the longest one if several start there.
+    fn last(&self, line: &[u8]) -> Option<(Range<usize>, u32)> {
+        let mut state = 0;
+        for (i, &b) in line.iter().enumerate().rev() {
+            state = self.backward.step(state, b);
+            // Scanning backwards, words are found where they start.
+            if let Some((len, n)) = self.backward.found[state] {
+                return Some((i..i + len, n));
+            }
+        }
+        None
+    }
+
+    /// Every word in `line`, overlapping ones included, by where they start and end.
+    fn all(&self, line: &[u8]) -> Vec<(Range<usize>, u32)> {
+        let mut found = vec![];
+        let mut state = 0;
+        for (i, &b) in line.iter().enumerate() {
+            state = self.forward.step(state, b);
+            for (len, n) in self.forward.words_at(state) {
+                found.push((i + 1 - len..i + 1, n));
+            }
+        }
+        found.sort_by_key(|(span, _)| (span.start, span.end));
+        found
+    }
+}
+
+/// An Aho–Corasick automaton as a DFA: a trie of the words where every state has a
+/// transition for every byte, falling back to the longest suffix that is also in the trie.
+/// Bytes in none of the words share a class, to keep the table small.
+struct Automaton {
+    class: [u8; 256],
+    classes: usize,
+    /// `next[state * classes + class]`, with state 0 the root.
+    next: Vec<u32>,
+    /// The length and value of the longest word ending in each state.
+    found: Vec<Option<(usize, u32)>>,
+    /// The word each state spells, if it is one.
+    word: Vec<Option<(usize, u32)>>,
+    fallback: Vec<usize>,
+}
+
+impl Automaton {
+    fn new(words: &[(Vec<u8>, u32)]) -> Self {
+        let mut class = [0u8; 256];
+        let mut classes = 1;
+        for &b in words.iter().flat_map(|(w, _)| w) {
+            if class[b as usize] == 0 {
+                class[b as usize] = classes as u8;
+                classes += 1;
+            }
+        }
+
+        // The trie, with `u32::MAX` for missing transitions.
+        let mut next = vec![u32::MAX; classes];
+        let mut found = vec![None];
+        for (w, n) in words {
+            let mut state = 0;
+            for &b in w {
+                let at = state * classes + class[b as usize] as usize;
+                if next[at] == u32::MAX {
+                    next[at] = found.len() as u32;
+                    next.extend(std::iter::repeat_n(u32::MAX, classes));
+                    found.push(None);
+                }
+                state = next[at] as usize;
+            }
+            found[state] = Some((w.len(), *n));
+        }
+
+        // Breadth first, so the fallback of every state is complete before its children.
+        let mut fallback = vec![0; found.len()];
+        let mut queue = VecDeque::new();
+        for next in &mut next[..classes] {
+            match *next {
+                u32::MAX => *next = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        let word = found.clone();
+        while let Some(state) = queue.pop_front() {
+            let back = fallback[state];
+            if found[state].is_none() {
+                found[state] = found[back];
+            }
+            for c in 0..classes {
+                let at = state * classes + c;
+                match next[at] {
+                    u32::MAX => next[at] = next[back * classes + c],
+                    child => {
+                        fallback[child as usize] = next[back * classes + c] as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Self {
+            class,
+            classes,
+            next,
+            found,
+            word,
+            fallback,
+        }
+    }
+
+    /// Every word ending in `state`, longest first.
+    fn words_at(&self, mut state: usize) -> impl Iterator<Item = (usize, u32)> + '_ {
+        std::iter::from_fn(move || {
+            while state != 0 {
+                let word = self.word[state];
+                state = self.fallback[state];
+                if word.is_some() {
+                    return word;
+                }
+            }
+            None
+        })
+    }
+
+    fn step(&self, state: usize, b: u8) -> usize {
+        self.next[state * self.classes + self.class[b as usize] as usize] as usize
+    }
+}
+
+#[aoc(day1, part2)]
+pub(crate) fn part2(input: &str) -> Result<u32> {
+    english().sum(input)
+}
+
+/// Part 2 by finding every digit in every line, to compare with the scanner, see
+/// `crate::bench`.
+#[cfg(test)]
+pub(crate) fn part2_sorted(input: &str) -> Result<u32> {
+    let mut sum: u32 = 0;
+    for (i, line) in input.lines().enumerate() {
+        let nums = nums_in_line(line);
+        let value = calibration_value(&nums).wrap_err_with(|| format!("line {}", i + 1))?;
+        sum = sum.checked_add(value).ok_or_else(overflow)?;
+    }
+    Ok(sum)
+}
+
+#[aoc_stream(day1, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<u32> {
+    sum_lines(input, |l| english().value(l))
+}
+
+/// What part 2 made of each line of an input, to find the line it reads differently than
+/// expected. Part 2 fails on any line in [`Diagnosis::skipped`] or [`Diagnosis::failed`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The lines with a calibration value.
+    pub values: Vec<Calibration>,
+    /// 1-based numbers of blank lines.
+    pub skipped: Vec<usize>,
+    /// 1-based numbers of the other lines without a digit.
+    pub failed: Vec<usize>,
+}
+
+/// The calibration value of a line, and the words it comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calibration {
+    /// 1-based.
+    pub line: usize,
+    /// Every word in the line, overlapping ones included, in order of where they start.
+    pub tokens: Vec<Token>,
+    pub first: Token,
+    pub last: Token,
+    pub value: u32,
+}
+
+/// A word or digit found in a line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    /// Byte offsets in the line.
+    pub span: Range<usize>,
+    /// The digit it stands for.
+    pub value: u32,
+}
+
+impl Diagnosis {
+    /// The sum of the calibration values, which is part 2's answer if no line failed.
+    pub fn sum(&self) -> u64 {
+        self.values.iter().map(|c| u64::from(c.value)).sum()
+    }
+}
+
+impl std::fmt::Display for Diagnosis {
+    /// A line per calibration value, with the line's words and the first and last in
+    /// brackets, e.g. `line 2: 83 [eight@0..5] two@4..7 [three@7..12]`, then the lines
+    /// without one and the sum.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in &self.values {
+            write!(f, "line {}: {}", c.line, c.value)?;
+            for t in &c.tokens {
+                let word = format!("{}@{}..{}", t.text, t.span.start, t.span.end);
+                if t == &c.first || t == &c.last {
+                    write!(f, " [{word}]")?;
+                } else {
+                    write!(f, " {word}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        if !self.skipped.is_empty() {
+            writeln!(f, "skipped blank lines: {}", self.skipped.iter().join(", "))?;
+        }
+        if !self.failed.is_empty() {
+            writeln!(f, "no digit in lines: {}", self.failed.iter().join(", "))?;
+        }
+        writeln!(f, "sum: {}", self.sum())
+    }
+}
+
+impl Scanner {
+    fn diagnose(&self, input: &str) -> Diagnosis {
+        let mut d = Diagnosis {
+            values: vec![],
+            skipped: vec![],
+            failed: vec![],
+        };
+        for (i, line) in input.lines().enumerate() {
+            let token = |(span, value): (Range<usize>, u32)| Token {
+                text: line[span.clone()].to_string(),
+                span,
+                value,
+            };
+            let bytes = line.as_bytes();
+            match (self.first(bytes), self.last(bytes)) {
+                (Some(first), Some(last)) => d.values.push(Calibration {
+                    line: i + 1,
+                    tokens: self.all(bytes).into_iter().map(token).collect(),
+                    value: first.1 * 10 + last.1,
+                    first: token(first),
+                    last: token(last),
+                }),
+                _ if line.trim().is_empty() => d.skipped.push(i + 1),
+                _ => d.failed.push(i + 1),
+            }
+        }
+        d
+    }
+}
+
+/// The sum of the calibration values, counting only digits.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day1};
+///
+/// assert_eq!(day1::solve_part1("1abc2\npqr3stu8vwx")?, Answer::Unsigned(12 + 38));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, part1)
+}
+
+/// The sum of the calibration values, counting digits spelled out in English too.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day1};
+///
+/// assert_eq!(day1::solve_part2("two1nine\neightwothree")?, Answer::Unsigned(29 + 83));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, part2)
+}
+
+/// The sum of the calibration values, counting digits spelled out in `vocabulary` too.
+/// See [`Vocabulary`] for an example.
+pub fn solve_part2_with(input: &str, vocabulary: &Vocabulary) -> Result<Answer> {
+    let scanner = Scanner::from(vocabulary);
+    solve(input, |input| scanner.sum(input))
+}
+
+/// [`solve_part2_with`] in a single pass over `input`.
+pub fn stream_part2_with(input: &mut dyn BufRead, vocabulary: &Vocabulary) -> Result<Answer> {
+    let scanner = Scanner::from(vocabulary);
+    Ok(Answer::from(sum_lines(input, |l| scanner.value(l))?))
+}
+
+/// What part 2 makes of each line of `input`.
+///
+/// ```
+/// use aoc_2023::y2023::day1;
+///
+/// let d = day1::diagnose("xtwone3\n\nnope");
+/// let line1 = &d.values[0];
+/// assert_eq!(line1.value, 23);
+/// assert_eq!(line1.first.text, "two");
+/// assert_eq!(line1.tokens[1].span, 3..6);
+/// assert_eq!((d.skipped, d.failed), (vec![2], vec![3]));
+/// ```
+pub fn diagnose(input: &str) -> Diagnosis {
+    english().diagnose(input)
+}
+
+/// What part 2 makes of each line of `input` with `vocabulary`.
+pub fn diagnose_with(input: &str, vocabulary: &Vocabulary) -> Diagnosis {
+    Scanner::from(vocabulary).diagnose(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{nums_in_line, part1, part2, Vocabulary};
+    use crate::answer::Answer;
+    use eyre::Result;
+
+    const EX1: &str = include_str!("../../input/2023/day1-ex1.txt");
+    const EX2: &str = include_str!("../../input/2023/day1-ex2.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EX1)?, 142);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nums_in_line() -> Result<()> {
+        let examples = [
+            ("two1nine", vec![2, 1, 9]),
+            ("eightwothree", vec![8, 2, 3]),
+            ("abcone2threexyz", vec![1, 2, 3]),
+            ("xtwone3four", vec![2, 1, 3, 4]),
+            ("4nineeightseven2", vec![4, 9, 8, 7, 2]),
+            ("zoneight234", vec![1, 8, 2, 3, 4]),
+            ("7pqrstsixteen", vec![7, 6]),
+        ];
+        for e in examples {
+            assert_eq!(nums_in_line(e.0), e.1);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn scanner() {
+        let examples = [
+            ("two1nine", 29),
+            ("eightwothree", 83),
+            ("xtwone3four", 24),
+            ("zoneight234", 14),
+            ("7pqrstsixteen", 76),
+            ("eightwo", 82),
+            ("oneight", 18),
+            ("nineeight", 98),
+            ("fffive", 55),
+        ];
+        for (line, value) in examples {
+            assert_eq!(super::english().value(line).ok(), Some(value), "{line}");
+        }
+        assert!(super::english().value("twelve").is_err());
+
+        // Longest first when several words start in the same place.
+        let s = super::Scanner::new(&[("a", 1), ("ab", 2), ("abc", 3), ("bc", 4), ("c", 5)]);
+        assert_eq!(s.first(b"xabcx"), Some((1..4, 3)));
+        assert_eq!(s.last(b"xabcx"), Some((3..4, 5)));
+        assert_eq!(s.first(b"xbabx"), Some((2..4, 2)));
+        assert_eq!(s.last(b"xbabx"), Some((2..4, 2)));
+        assert_eq!(s.first(b"xyz"), None);
+    }
+
+    #[test]
+    fn vocabularies() -> Result<()> {
+        let value = |name, line| {
+            let v = Vocabulary::named(name).unwrap();
+            super::Scanner::from(&v).value(line).ok()
+        };
+        assert_eq!(value("english", "eightwothree"), Some(83));
+        assert_eq!(value("swedish", "xtvåttsju"), Some(27));
+        assert_eq!(value("swedish", "two1nine"), Some(11));
+        assert_eq!(value("german", "fünfundzwanzig"), Some(55));
+        assert_eq!(value("german", "sechsiebenull"), Some(60));
+        // Words overlap, so the `V` of `IV` starts last.
+        assert_eq!(value("roman", "VIIIxIV"), Some(85));
+        assert_eq!(value("roman", "MMXXIII"), Some(31));
+        assert_eq!(Vocabulary::named("klingon"), None);
+        assert_eq!(Vocabulary::load("english")?, Vocabulary::english());
+
+        let v = "# Danish\n\nen 1\n  to 2 \n".parse::<Vocabulary>()?;
+        assert_eq!(v.words(), [("en".to_string(), 1), ("to".to_string(), 2)]);
+        assert_eq!(
+            super::solve_part2_with("tentoen\n3", &v)?,
+            Answer::Unsigned(11 + 33)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_vocabulary() {
+        let e = |s: &str| format!("{:#}", s.parse::<Vocabulary>().unwrap_err());
+        assert_eq!(e("one 1\ntwo 12"), "line 2, column 5: `12` is not a digit");
+        assert_eq!(
+            e("one 1\n\nthree"),
+            "line 3: expected a word and its digit, e.g. `two 2`"
+        );
+        assert_eq!(e("# nothing"), "no words");
+        let e = Vocabulary::load("no/such/file").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "reading no/such/file, which isn't one of the vocabularies english, swedish, german, roman"
+        );
+    }
+
+    #[test]
+    fn diagnose() -> Result<()> {
+        let d = super::diagnose(EX2);
+        assert_eq!(d.sum(), 281);
+        assert_eq!(d.values.len(), 7);
+        let l = &d.values[1];
+        assert_eq!(l.value, 83);
+        let spans = l.tokens.iter().map(|t| t.span.clone()).collect::<Vec<_>>();
+        assert_eq!(spans, [0..5, 4..7, 7..12]);
+        assert_eq!((l.first.span.clone(), l.last.span.clone()), (0..5, 7..12));
+
+        let roman = Vocabulary::named("roman").unwrap();
+        let d = super::diagnose_with("xVIIIx\n \nx", &roman);
+        assert_eq!(
+            d.to_string(),
+            "line 1: 81 V@1..2 VI@1..3 VII@1..4 [VIII@1..5] I@2..3 II@2..4 III@2..5 I@3..4 \
+             II@3..5 [I@4..5]\n\
+             skipped blank lines: 2\n\
+             no digit in lines: 3\n\
+             sum: 81\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EX2)?, 281);
+        assert_eq!(super::part2_sorted(EX2)?, 281);
+        let input = include_str!("../../input/2023/day1.txt");
+        assert_eq!(part2(input)?, super::part2_sorted(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = include_str!("../../input/2023/day1.txt");
+        assert_eq!(super::part1_stream(&mut EX1.as_bytes())?, 142);
+        assert_eq!(super::part2_stream(&mut EX2.as_bytes())?, 281);
+        assert_eq!(super::part1_stream(&mut input.as_bytes())?, part1(input)?);
+        assert_eq!(super::part2_stream(&mut input.as_bytes())?, part2(input)?);
+        let e = super::part2_stream(&mut "two1nine\r\nzero\n\n".as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: no digit");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = part1("1abc2\ntrebuchet").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: no digit");
+        let e = part2("two1nine\nthreebuchet\nzero").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 3: no digit");
+    }
+}
@@ -0,0 +1,452 @@
+//! Day 10: Pipe Maze. A loop of pipes through a [`Map`] of [`Tile`]s.
+
+use crate::answer::{solve, Answer};
+use crate::grid::{Dir, Grid, Pos};
+use crate::parse::error_at_position;
+use crate::render::{Picture, Rgb};
+use aoc_macros::{aoc, aoc_generator, aoc_visual};
+use eyre::{eyre, Report, Result};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+use tracing::{debug, trace};
+
+/// The field of pipes, with the start in it. Parses and prints as the puzzle input.
+///
+/// ```
+/// use aoc_2023::y2023::day10::{Map, Tile};
+///
+/// let map: Map = ".....\n.S-7.\n.|.|.\n.L-J.\n.....".parse()?;
+/// let (start, tile) = map.find_start()?;
+/// assert_eq!(tile, Tile::SE);
+/// let mut path = vec![];
+/// let steps = map.walk_loop(start, tile, |pos, _| path.push((pos.x, pos.y)))?;
+/// assert_eq!(steps, 4);
+/// // Not the start, but the farthest tile twice, once from each end.
+/// assert_eq!(path.len(), 8);
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Map(Grid<Tile>);
+
+impl Map {
+    /// Where the start is, and the pipe that it must be to connect to exactly two of its
+    /// neighbours.
+    pub fn find_start(&self) -> Result<(Pos, Tile)> {
+        let s = Pos::from(
+            self.0
+                .find(Tile::is_start)
+                .ok_or_else(|| eyre!("missing start"))?,
+        );
+        let dirs = Dir::ALL
+            .iter()
+            .filter(|&d| {
+                self.get(s.step(*d))
+                    .and_then(|t| t.traverse(d.inv()))
+                    .is_some()
+            })
+            .cloned()
+            .collect_vec();
+        if dirs.len() != 2 {
+            let msg = format!("start connects to {} pipes", dirs.len());
+            return Err(error_at(s, msg));
+        }
+        let t = Tile::from_directions((dirs[0], dirs[1]));
+        debug!(x = s.x, y = s.y, tile = %t, "start");
+        Ok((s, t))
+    }
+
+    /// Follows the loop from `s` in both directions at once, calling `visit`
+    /// on every tile, until the two ends meet. Returns the number of steps.
+    pub fn walk_loop(&self, s: Pos, t: Tile, mut visit: impl FnMut(Pos, Tile)) -> Result<usize> {
+        let dirs = t
+            .dirs()
+            .ok_or_else(|| error_at(s, format!("{t} is not a pipe")))?;
+        let mut v = [(s, dirs.0), (s, dirs.1)];
+        for i in 1.. {
+            for (end, x) in v.iter_mut().enumerate() {
+                let (pos, dir) = *x;
+                let new_pos = pos.step(dir);
+                let t = self
+                    .get(new_pos)
+                    .ok_or_else(|| error_at(pos, "pipe leads off the map"))?;
+                debug!(step = i, end, x = new_pos.x, y = new_pos.y, tile = %t, "walk");
+                visit(new_pos, t);
+                let new_dir = t.traverse(dir.inv()).ok_or_else(|| {
+                    error_at(new_pos, format!("{t} does not connect {:?}", dir.inv()))
+                })?;
+                *x = (new_pos, new_dir);
+            }
+            if v[0].0 == v[1].0 {
+                return Ok(i);
+            }
+        }
+        unreachable!()
+    }
+
+    /// The tile at `p`, if it is on the map.
+    pub fn get(&self, p: Pos) -> Option<Tile> {
+        self.0.get(p).copied()
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn new(width: usize, height: usize) -> Self {
+        Self(Grid::new(width, height, Tile::Ground))
+    }
+
+    fn put(&mut self, pos: Pos, tile: Tile) {
+        self.0.set(pos, tile);
+    }
+}
+
+/// An error at `p`, which is on the map.
+fn error_at(p: Pos, msg: impl std::fmt::Display) -> Report {
+    error_at_position(p.y as usize + 1, p.x as usize + 1, msg)
+}
+
+impl std::str::FromStr for Map {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A tile of the map, named after the directions a pipe connects.
+#[derive(Clone, Copy, Debug, Display, FromStr, PartialEq, Eq)]
+pub enum Tile {
+    #[display("|")]
+    NS,
+    #[display("-")]
+    EW,
+    #[display("L")]
+    NE,
+    #[display("J")]
+    NW,
+    #[display("7")]
+    SW,
+    #[display("F")]
+    SE,
+    #[display(".")]
+    Ground,
+    #[display("S")]
+    Start,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = parse_display::ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+impl Tile {
+    /// The direction a pipe leads when entered from `from`, if it connects that way.
+    pub fn traverse(&self, from: Dir) -> Option<Dir> {
+        let result = match from {
+            Dir::N => match self {
+                Tile::NS => Some(Dir::S),
+                Tile::NE => Some(Dir::E),
+                Tile::NW => Some(Dir::W),
+                _ => None,
+            },
+            Dir::S => match self {
+                Tile::NS => Some(Dir::N),
+                Tile::SW => Some(Dir::W),
+                Tile::SE => Some(Dir::E),
+                _ => None,
+            },
+            Dir::E => match self {
+                Tile::EW => Some(Dir::W),
+                Tile::NE => Some(Dir::N),
+                Tile::SE => Some(Dir::S),
+                _ => None,
+            },
+            Dir::W => match self {
+                Tile::EW => Some(Dir::E),
+                Tile::NW => Some(Dir::N),
+                Tile::SW => Some(Dir::S),
+                _ => None,
+            },
+        };
+        trace!(tile = %self, ?from, to = ?result, "traverse");
+        result
+    }
+
+    fn from_directions(pair: (Dir, Dir)) -> Self {
+        let mut v = [pair.0, pair.1];
+        v.sort();
+        match (v[0], v[1]) {
+            (Dir::N, Dir::S) => Tile::NS,
+            (Dir::N, Dir::E) => Tile::NE,
+            (Dir::N, Dir::W) => Tile::NW,
+            (Dir::E, Dir::S) => Tile::SE,
+            (Dir::S, Dir::W) => Tile::SW,
+            (Dir::E, Dir::W) => Tile::EW,
+            _ => unreachable!("{v:?} are not two distinct directions"),
+        }
+    }
+
+    /// The directions a pipe connects. `None` for the ground and the start.
+    pub fn dirs(&self) -> Option<(Dir, Dir)> {
+        match self {
+            Tile::NS => Some((Dir::N, Dir::S)),
+            Tile::EW => Some((Dir::E, Dir::W)),
+            Tile::NE => Some((Dir::N, Dir::E)),
+            Tile::NW => Some((Dir::N, Dir::W)),
+            Tile::SW => Some((Dir::S, Dir::W)),
+            Tile::SE => Some((Dir::S, Dir::E)),
+            Tile::Ground | Tile::Start => None,
+        }
+    }
+
+    /// The tile drawn with box-drawing characters, for pictures.
+    fn symbol(&self) -> char {
+        match self {
+            Tile::NS => '│',
+            Tile::EW => '─',
+            Tile::NE => '└',
+            Tile::NW => '┘',
+            Tile::SW => '┐',
+            Tile::SE => '┌',
+            Tile::Ground => '.',
+            Tile::Start => 'S',
+        }
+    }
+
+    fn is_start(&self) -> bool {
+        matches!(self, Self::Start)
+    }
+
+    /// `None` if the tile can't follow `i`, which only happens off a closed loop.
+    fn inout(&self, i: InOut) -> Option<InOut> {
+        let (t, b) = (i.2, i.3);
+
+        let (tr, br) = match (t, b) {
+            (true, true) => match &self {
+                Tile::NS => (false, false),
+                Tile::NE => (false, true),
+                Tile::SE => (true, false),
+                Tile::Ground => (true, true),
+                _ => return None,
+            },
+            (true, false) => match &self {
+                Tile::EW => (true, false),
+                Tile::NW => (false, false),
+                Tile::SW => (true, true),
+                _ => return None,
+            },
+            (false, true) => match &self {
+                Tile::EW => (false, true),
+                Tile::NW => (true, true),
+                Tile::SW => (false, false),
+                _ => return None,
+            },
+            (false, false) => match &self {
+                Tile::NS => (true, true),
+                Tile::NE => (true, false),
+                Tile::SE => (false, true),
+                Tile::Ground => (false, false),
+                _ => return None,
+            },
+        };
+
+        Some(InOut(t, b, tr, br))
+    }
+}
+
+/// InOut
+///
+/// (NW, SW, NE, SE)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct InOut(bool, bool, bool, bool);
+
+impl InOut {
+    fn new() -> Self {
+        Self(false, false, false, false)
+    }
+    fn all_inside(&self) -> bool {
+        matches!(self, Self(true, true, true, true))
+    }
+}
+
+#[aoc_generator(day10)]
+pub(crate) fn gen(input: &str) -> Result<Map> {
+    input.parse()
+}
+
+#[aoc(day10, part1)]
+pub(crate) fn part1(map: &Map) -> Result<usize> {
+    let (s, t) = map.find_start()?;
+    map.walk_loop(s, t, |_, _| ())
+}
+
+/// The loop on otherwise empty ground, with the start replaced by its pipe.
+fn loop_only(map: &Map) -> Result<Map> {
+    let (s, t) = map.find_start()?;
+    let mut m = Map::new(map.width(), map.height());
+    m.put(s, t);
+    map.walk_loop(s, t, |pos, t| m.put(pos, t))?;
+    Ok(m)
+}
+
+/// Calls `inside` on every tile enclosed by `m`, the output of [`loop_only`].
+fn enclosed(m: &Map, mut inside: impl FnMut(Pos)) -> Result<()> {
+    for (y, line) in m.0.rows().enumerate() {
+        let mut i = InOut::new();
+        for (x, tile) in line.iter().enumerate() {
+            let pos = Pos::new(x as isize, y as isize);
+            i = tile
+                .inout(i)
+                .ok_or_else(|| error_at(pos, format!("{tile} breaks the loop")))?;
+            trace!(x, y, %tile, state = ?i, inside = i.all_inside(), "scan");
+            if i.all_inside() {
+                inside(pos);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[aoc(day10, part2)]
+pub(crate) fn part2(map: &Map) -> Result<usize> {
+    let mut count = 0;
+    enclosed(&loop_only(map)?, |_| count += 1)?;
+    Ok(count)
+}
+
+/// The loop in yellow from the start in red, and the tiles it encloses in green.
+#[aoc_visual(day10)]
+pub(crate) fn visualize(input: &str) -> Result<Picture> {
+    let map = gen(input)?;
+    let (s, _) = map.find_start()?;
+    let m = loop_only(&map)?;
+    let mut pic = Picture::new(&map.0.map(Tile::symbol));
+    for (p, t) in m.0.cells() {
+        if *t != Tile::Ground {
+            pic.paint(p, Rgb::YELLOW);
+        }
+    }
+    pic.paint(s, Rgb::RED);
+    enclosed(&m, |p| pic.paint(p, Rgb::GREEN))?;
+    pic.legend(Rgb::RED, "start");
+    pic.legend(Rgb::YELLOW, "loop");
+    pic.legend(Rgb::GREEN, "enclosed");
+    Ok(pic)
+}
+
+/// The steps along the loop to the tile farthest from the start.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day10};
+///
+/// let maze = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+/// assert_eq!(day10::solve_part1(maze)?, Answer::Unsigned(4));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&gen(input)?))
+}
+
+/// The number of tiles enclosed by the loop.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day10};
+///
+/// let maze = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+/// assert_eq!(day10::solve_part2(maze)?, Answer::Unsigned(1));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&gen(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    use eyre::Result;
+
+    const EX1: &str = include_str!("../../input/2023/day10-ex1.txt");
+    const EX2: &str = include_str!("../../input/2023/day10-ex2.txt");
+    const EX3: &str = include_str!("../../input/2023/day10-ex3.txt");
+    const EX4: &str = include_str!("../../input/2023/day10-ex4.txt");
+    const INPUT: &str = include_str!("../../input/2023/day10.txt");
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for input in [EX1, EX2, EX3, EX4, INPUT] {
+            crate::parse::assert_round_trip::<super::Map>(input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_test() -> Result<()> {
+        let _ex1 = EX1.parse::<super::Map>()?;
+        let _ex2 = EX2.parse::<super::Map>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn p1() -> Result<()> {
+        assert_eq!(super::part1(&super::gen(EX1)?)?, 4);
+        assert_eq!(super::part1(&super::gen(EX2)?)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn p2a() -> Result<()> {
+        assert_eq!(super::part2(&super::gen(EX3)?)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn p2b() -> Result<()> {
+        assert_eq!(super::part2(&super::gen(EX4)?)?, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<()> {
+        use crate::render::Rgb;
+        let key = [(Rgb::RED, 's'), (Rgb::YELLOW, 'l'), (Rgb::GREEN, 'i')];
+        let pic = super::visualize(EX3)?;
+        let expected = "...........\n.sllllllll.\n.lllllllll.\n.ll.....ll.\n.ll.....ll.\n\
+                        .llll.llll.\n.liil.liil.\n.llll.llll.\n...........";
+        assert_eq!(pic.sketch(&key), expected);
+        // Pipes off the loop are drawn too, uncoloured.
+        let pic = super::visualize("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF")?;
+        assert_eq!(pic.sketch(&key), "─└│┌┐\n┐sll│\n└lil│\n─lll│\n└│─┘┌");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() -> Result<()> {
+        let e = super::gen(".....\n.S-7.\n.|X|.").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 3: bad cell 'X': parse failed."
+        );
+        let e = super::part1(&super::gen(".....\n..-7.\n.|.|.")?).unwrap_err();
+        assert_eq!(e.to_string(), "missing start");
+        let e = super::part1(&super::gen(".|...\n-S-7.\n.|.|.")?).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 2: start connects to 4 pipes");
+        let e = super::part2(&super::gen(".....\n.S-7.\n.|.|.\n.L-|.")?).unwrap_err();
+        assert_eq!(e.to_string(), "line 4, column 4: | does not connect W");
+        let e = super::part1(&super::gen("S-7\n|.|")?).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: pipe leads off the map");
+        Ok(())
+    }
+}
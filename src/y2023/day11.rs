@@ -0,0 +1,244 @@
+//! Day 11: Cosmic Expansion. Distances between galaxies in an expanding universe.
+
+use crate::answer::{solve, Answer};
+use crate::grid::Grid;
+use crate::parse::overflow;
+use crate::render::{Picture, Rgb};
+use aoc_macros::{aoc, aoc_generator, aoc_visual};
+use eyre::Result;
+use tracing::{debug, trace};
+
+/// The number of galaxies in each column and each row of the image, which is all the
+/// distances depend on. Parses from the image.
+///
+/// ```
+/// use aoc_2023::y2023::day11::Counts;
+///
+/// let counts: Counts = "#..\n...\n..#".parse()?;
+/// assert_eq!(counts.distance_sum(1)?, 4);
+/// assert_eq!(counts.distance_sum(10)?, 22);
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub struct Counts(Vec<usize>, Vec<usize>);
+
+impl Counts {
+    /// The sum of the distances between every pair of galaxies, with every empty row and
+    /// column `expansion` times as wide. Fails if it overflows.
+    pub fn distance_sum(&self, expansion: usize) -> Result<usize> {
+        self.sum_all_distances(expansion, sum_distances)
+            .ok_or_else(overflow)
+    }
+
+    fn sum_all_distances(
+        &self,
+        expansion: usize,
+        sum: fn(&[usize], usize) -> Option<usize>,
+    ) -> Option<usize> {
+        let (columns, rows) = (sum(&self.0, expansion)?, sum(&self.1, expansion)?);
+        debug!(expansion, columns, rows, "distance sums");
+        columns.checked_add(rows)
+    }
+}
+
+impl std::str::FromStr for Counts {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let g = Grid::parse_with(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected '#' or '.'"),
+        })?;
+        let x = g.columns().map(|c| c.filter(|&&x| x).count()).collect();
+        let y = g.rows().map(|r| r.iter().filter(|&&x| x).count()).collect();
+
+        Ok(Counts(x, y))
+    }
+}
+
+#[aoc_generator(day11)]
+pub(crate) fn gen(input: &str) -> Result<Counts> {
+    input.parse()
+}
+
+/// `None` if the sum overflows.
+fn sum_distances(v: &[usize], expansion: usize) -> Option<usize> {
+    let total = v.iter().try_fold(0usize, |t, &n| t.checked_add(n))?;
+    let mut rest = total;
+    let mut active_edges = 0usize;
+    let mut active_nodes = 0usize;
+    let mut sum = 0;
+    for &n in v {
+        if n == 0 {
+            sum = active_edges.checked_mul(expansion)?.checked_add(sum)?;
+            trace!(n, active_nodes, active_edges, sum, "step");
+        } else {
+            sum = active_edges.checked_add(sum)?;
+            rest -= n;
+            let new = n.checked_mul(rest)?;
+            let end = active_nodes.checked_mul(n)?;
+            // The edges ending here were counted in `active_edges`.
+            active_edges = active_edges.checked_sub(end)?.checked_add(new)?;
+            active_nodes += n;
+            trace!(n, active_nodes, active_edges, sum, "step");
+        }
+    }
+    debug_assert_eq!(active_edges, 0);
+    debug_assert_eq!(active_nodes, total);
+    debug_assert_eq!(rest, 0);
+    Some(sum)
+}
+
+/// Expands the coordinate of every galaxy and adds up the distance of every pair. `None`
+/// if the coordinates or the sum overflow.
+fn sum_distances_naive(v: &[usize], expansion: usize) -> Option<usize> {
+    let mut galaxies = vec![];
+    let mut at = 0usize;
+    for &n in v {
+        galaxies.extend(std::iter::repeat_n(at, n));
+        at = at.checked_add(if n == 0 { expansion } else { 1 })?;
+    }
+    let mut sum = 0usize;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            sum = sum.checked_add(b - a)?;
+        }
+    }
+    Some(sum)
+}
+
+#[aoc(day11, part1)]
+pub(crate) fn part1(input: &Counts) -> Result<usize> {
+    input
+        .sum_all_distances(2, sum_distances)
+        .ok_or_else(overflow)
+}
+
+#[aoc(day11, part1, naive)]
+pub(crate) fn part1_naive(input: &Counts) -> Result<usize> {
+    input
+        .sum_all_distances(2, sum_distances_naive)
+        .ok_or_else(overflow)
+}
+
+#[aoc(day11, part2)]
+pub(crate) fn part2(input: &Counts) -> Result<usize> {
+    input
+        .sum_all_distances(1000000, sum_distances)
+        .ok_or_else(overflow)
+}
+
+#[aoc(day11, part2, naive)]
+pub(crate) fn part2_naive(input: &Counts) -> Result<usize> {
+    input
+        .sum_all_distances(1000000, sum_distances_naive)
+        .ok_or_else(overflow)
+}
+
+/// The galaxies in yellow, and the empty rows and columns, which expand, in blue.
+#[aoc_visual(day11)]
+pub(crate) fn visualize(input: &str) -> Result<Picture> {
+    let Counts(columns, rows) = input.parse()?;
+    let grid = input.parse::<Grid<char>>()?;
+    let mut pic = Picture::new(&grid);
+    for (p, &c) in grid.cells() {
+        if c == '#' {
+            pic.paint(p, Rgb::YELLOW);
+        } else if columns[p.x] == 0 || rows[p.y] == 0 {
+            pic.paint(p, Rgb::BLUE);
+        }
+    }
+    pic.legend(Rgb::YELLOW, "galaxy");
+    pic.legend(Rgb::BLUE, "empty, expanded");
+    Ok(pic)
+}
+
+/// The sum of the distances between every pair of galaxies, with every empty row and
+/// column twice as wide.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day11};
+///
+/// let image = "#..\n...\n..#";
+/// assert_eq!(day11::solve_part1(image)?, Answer::Unsigned(6));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&gen(input)?))
+}
+
+/// The sum of the distances between every pair of galaxies, with every empty row and
+/// column a million times as wide.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day11};
+///
+/// let image = "#..\n...\n..#";
+/// assert_eq!(day11::solve_part2(image)?, Answer::Unsigned(2_000_002));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&gen(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    const EX: &str = include_str!("../../input/2023/day11-ex1.txt");
+
+    #[test]
+    fn p1() -> eyre::Result<()> {
+        assert_eq!(super::part1(&super::gen(EX)?)?, 374);
+        Ok(())
+    }
+
+    #[test]
+    fn sd() {
+        assert_eq!(super::sum_distances(&[1, 0, 1], 2), Some(3));
+        assert_eq!(super::sum_distances(&[1, 1, 1], 2), Some(4));
+        assert_eq!(super::sum_distances(&[1, 0, 1], usize::MAX), None);
+        assert_eq!(super::sum_distances_naive(&[1, 0, 1], usize::MAX), None);
+        assert_eq!(super::sum_distances(&[usize::MAX, 1], 2), None);
+        let wide = [1, 0, 0, 0, 1];
+        assert_eq!(super::sum_distances(&wide, usize::MAX / 2), None);
+        assert_eq!(super::sum_distances_naive(&wide, usize::MAX / 2), None);
+        for v in [&[1, 0, 1][..], &[1, 1, 1], &[0, 3, 0, 0, 2, 1, 0], &[]] {
+            for expansion in [1, 2, 10] {
+                assert_eq!(
+                    super::sum_distances(v, expansion),
+                    super::sum_distances_naive(v, expansion),
+                    "{v:?} expanded {expansion} times"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn p2() -> eyre::Result<()> {
+        let counts = super::gen(EX)?;
+        for sum in [super::sum_distances, super::sum_distances_naive] {
+            assert_eq!(counts.sum_all_distances(10, sum), Some(1030));
+            assert_eq!(counts.sum_all_distances(100, sum), Some(8410));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> eyre::Result<()> {
+        use crate::render::Rgb;
+        let key = [(Rgb::YELLOW, 'g'), (Rgb::BLUE, 'e')];
+        let pic = super::visualize("#...\n....\n...#")?;
+        assert_eq!(pic.sketch(&key), "gee.\neeee\n.eeg");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = super::gen("#..\n.x.").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: bad cell 'x': expected '#' or '.'"
+        );
+        let e = super::gen("#..\n.#").err().unwrap();
+        assert_eq!(e.to_string(), "line 2: expected 3 columns, found 2");
+    }
+}
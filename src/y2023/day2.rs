@@ -0,0 +1,622 @@
+//! Day 2: Cube Conundrum. Games of cubes drawn from a bag.
+
+use std::io::BufRead;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{self, error_at, overflow, wrap_at};
+use crate::stream;
+use aoc_macros::{aoc, aoc_generator, aoc_stream};
+use eyre::{bail, eyre, Result, WrapErr};
+use itertools::Itertools;
+use parse_display::Display;
+
+/// A game, e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green`: rounds of draws from the bag,
+/// which go back in after each round.
+#[derive(Display, PartialEq, Debug)]
+#[display("Game {index}: {runs}")]
+pub struct Game {
+    index: u32,
+    runs: Draws,
+}
+
+impl std::str::FromStr for Game {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (head, runs) = s
+            .split_once(':')
+            .ok_or_else(|| eyre!("expected `Game <n>: <draws>`"))?;
+        let index = head
+            .strip_prefix("Game ")
+            .ok_or_else(|| error_at(s, head, "expected `Game <n>`"))?;
+        let index = index.parse().map_err(|e| error_at(s, index, e))?;
+        let runs = Draws::parse_in(s, runs)?;
+        Ok(Game { index, runs })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Draws(Vec<Vec<Draw>>);
+
+impl Draws {
+    /// Parses `s`, a part of `line`, with error columns relative to `line`.
+    fn parse_in(line: &str, s: &str) -> Result<Self> {
+        let x = s
+            .split(';')
+            .map(|draws| {
+                draws
+                    .split(',')
+                    .map(|d| {
+                        let d = d.trim();
+                        d.parse::<Draw>()
+                            .map_err(|e| wrap_at(e, line, d, format!("bad draw {d:?}")))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Draws(x))
+    }
+}
+
+impl std::str::FromStr for Draws {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_in(s, s)
+    }
+}
+
+impl std::fmt::Display for Draws {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let formatter = self.0.iter().format_with("; ", |row, f| {
+            f(&row.iter().format_with(", ", |elt, g| g(&elt)))
+        });
+        write!(f, "{formatter}")
+    }
+}
+
+/// Some cubes of one colour, e.g. `3 blue`. Colours are any word.
+#[derive(Display, PartialEq, Eq, Debug, Clone)]
+#[display("{count} {colour}")]
+pub struct Draw {
+    count: u32,
+    colour: String,
+}
+
+impl std::str::FromStr for Draw {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((count, colour)) = s.split_once(' ') else {
+            bail!("expected `<count> <colour>`");
+        };
+        if colour.is_empty() || colour.contains(char::is_whitespace) {
+            bail!("expected a colour");
+        }
+        Ok(Draw {
+            count: count.parse()?,
+            colour: colour.to_string(),
+        })
+    }
+}
+
+impl Draw {
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn colour(&self) -> &str {
+        &self.colour
+    }
+}
+
+/// The most cubes of each colour, e.g. `red=12,green=13,blue=14`, which is the default.
+/// Colours not in the bag have none.
+///
+/// ```
+/// use aoc_2023::y2023::day2::Bag;
+///
+/// let bag: Bag = "red=1,purple=2".parse()?;
+/// assert_eq!(bag.get("purple"), 2);
+/// assert_eq!(bag.get("blue"), 0);
+/// assert_eq!(Bag::default().to_string(), "red=12,green=13,blue=14");
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bag(Vec<(String, u32)>);
+
+impl Default for Bag {
+    fn default() -> Self {
+        Bag(vec![
+            ("red".to_string(), 12),
+            ("green".to_string(), 13),
+            ("blue".to_string(), 14),
+        ])
+    }
+}
+
+impl Bag {
+    /// The cubes of `colour`.
+    pub fn get(&self, colour: &str) -> u32 {
+        self.0
+            .iter()
+            .find(|(c, _)| c == colour)
+            .map_or(0, |&(_, n)| n)
+    }
+
+    /// The colours and their cubes, in order.
+    pub fn colours(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.0.iter().map(|(c, n)| (c.as_str(), *n))
+    }
+
+    /// Whether the bag has the cubes of `draw`.
+    pub fn allows(&self, draw: &Draw) -> bool {
+        draw.count <= self.get(&draw.colour)
+    }
+
+    /// Raises the cubes of `colour` to `n`, adding the colour if it's missing.
+    fn raise(&mut self, colour: &str, n: u32) {
+        match self.0.iter_mut().find(|(c, _)| c == colour) {
+            Some((_, m)) => *m = (*m).max(n),
+            None => self.0.push((colour.to_string(), n)),
+        }
+    }
+}
+
+impl std::str::FromStr for Bag {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut bag = Bag(vec![]);
+        for entry in s.split(',') {
+            let Some((colour, n)) = entry.trim().split_once('=') else {
+                bail!("bad bag entry `{entry}`, expected `<colour>=<count>`");
+            };
+            if colour.is_empty() || colour.contains(char::is_whitespace) {
+                bail!("bad colour `{colour}`");
+            }
+            if bag.0.iter().any(|(c, _)| c == colour) {
+                bail!("`{colour}` is in the bag twice");
+            }
+            let n = n
+                .parse()
+                .wrap_err_with(|| format!("bad count `{n}` for `{colour}`"))?;
+            bag.0.push((colour.to_string(), n));
+        }
+        Ok(bag)
+    }
+}
+
+impl std::fmt::Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self
+            .0
+            .iter()
+            .format_with(",", |(c, n), f| f(&format_args!("{c}={n}")));
+        write!(f, "{entries}")
+    }
+}
+
+/// The colours of the puzzle, which part 2 multiplies whether or not a game draws them.
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// A draw that needs more cubes than a bag has, see [`Game::impossible_draw`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImpossibleDraw<'a> {
+    /// 0-based index of the round in the game.
+    pub round: usize,
+    pub draw: &'a Draw,
+}
+
+impl Game {
+    /// The number after `Game`.
+    pub fn id(&self) -> u32 {
+        self.index
+    }
+
+    /// The draws of each round.
+    pub fn rounds(&self) -> &[Vec<Draw>] {
+        &self.runs.0
+    }
+
+    fn draws(&self) -> impl Iterator<Item = &Draw> {
+        self.runs.0.iter().flatten()
+    }
+
+    /// Whether `bag` has the cubes of every draw.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.draws().all(|d| bag.allows(d))
+    }
+
+    /// The first draw that needs more cubes than `bag` has, if any.
+    pub fn impossible_draw(&self, bag: &Bag) -> Option<ImpossibleDraw<'_>> {
+        self.rounds().iter().enumerate().find_map(|(round, draws)| {
+            let draw = draws.iter().find(|d| !bag.allows(d))?;
+            Some(ImpossibleDraw { round, draw })
+        })
+    }
+
+    /// The fewest cubes of each colour that make the game possible, in order of the
+    /// colours' first draws.
+    pub fn fewest(&self) -> Bag {
+        let mut fewest = Bag(vec![]);
+        for d in self.draws() {
+            fewest.raise(&d.colour, d.count);
+        }
+        fewest
+    }
+
+    /// The product of the fewest cubes of red, green, blue and any other colour drawn, or
+    /// `None` if it overflows. It's 0 if red, green or blue is never drawn.
+    fn power(&self) -> Option<u32> {
+        let fewest = self.fewest();
+        let others = fewest
+            .0
+            .iter()
+            .filter(|(c, _)| !COLOURS.contains(&c.as_str()));
+        COLOURS
+            .iter()
+            .map(|c| fewest.get(c))
+            .chain(others.map(|&(_, n)| n))
+            .try_fold(1u32, u32::checked_mul)
+    }
+}
+
+#[aoc_generator(day2)]
+pub(crate) fn generator2(input: &str) -> Result<Vec<Game>> {
+    parse::lines(input, str::parse)
+}
+
+/// The games of `input`, one per line, to query with [`possible`], [`smallest_bag`] and
+/// the methods of [`Game`].
+///
+/// ```
+/// use aoc_2023::y2023::day2::{self, Bag};
+///
+/// let games = day2::games(
+///     "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+///      Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
+///      Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+/// )?;
+/// let bag = Bag::default();
+/// let ids = day2::possible(&games, &bag).map(|g| g.id()).collect::<Vec<_>>();
+/// assert_eq!(ids, [1, 2]);
+///
+/// let why = games[2].impossible_draw(&bag).unwrap();
+/// assert_eq!((why.round, why.draw.to_string()), (0, "20 red".to_string()));
+/// assert_eq!(games[2].fewest().to_string(), "green=13,blue=6,red=20");
+///
+/// let bag = day2::smallest_bag(&games, 2).unwrap();
+/// assert_eq!(bag.to_string(), "blue=6,red=4,green=3");
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn games(input: &str) -> Result<Vec<Game>> {
+    generator2(input)
+}
+
+/// The games possible with `bag`.
+pub fn possible<'a>(games: &'a [Game], bag: &'a Bag) -> impl Iterator<Item = &'a Game> {
+    games.iter().filter(|g| g.is_possible(bag))
+}
+
+/// One of the bags with the fewest cubes in total that make at least `n` of the games
+/// possible, with the colours in order of their first draws, or `None` if there are fewer
+/// games. Takes time polynomial in the number of games, of a degree one less than the
+/// number of colours.
+pub fn smallest_bag(games: &[Game], n: usize) -> Option<Bag> {
+    if n > games.len() {
+        return None;
+    }
+    if n == 0 {
+        return Some(Bag(vec![]));
+    }
+    let fewest = games.iter().map(Game::fewest).collect_vec();
+    let mut colours: Vec<&str> = vec![];
+    for (c, _) in fewest.iter().flat_map(Bag::colours) {
+        if !colours.contains(&c) {
+            colours.push(c);
+        }
+    }
+    // What each game needs of each colour.
+    let needs = fewest
+        .iter()
+        .map(|f| colours.iter().map(|c| f.get(c)).collect_vec())
+        .collect_vec();
+    let mut search = BagSearch {
+        needs,
+        n,
+        counts: vec![],
+        best: None,
+    };
+    search.run(&(0..games.len()).collect_vec(), 0);
+    let (_, counts) = search.best?;
+    let bag = colours.iter().zip(counts).map(|(c, n)| (c.to_string(), n));
+    Some(Bag(bag.collect()))
+}
+
+/// Branch and bound for [`smallest_bag`], picking a count for each colour in turn from
+/// what the games still possible need of it. The last colour's count is the least that
+/// lets `n` of those games through.
+struct BagSearch {
+    needs: Vec<Vec<u32>>,
+    n: usize,
+    /// The counts picked so far.
+    counts: Vec<u32>,
+    /// The total and counts of the best bag yet.
+    best: Option<(u64, Vec<u32>)>,
+}
+
+impl BagSearch {
+    /// Picks the remaining counts for the games in `fit`, which fit the counts so far and
+    /// need `total` cubes.
+    fn run(&mut self, fit: &[usize], total: u64) {
+        let colour = self.counts.len();
+        let colours = self.needs.first().map_or(0, Vec::len);
+        if self.best.as_ref().is_some_and(|b| b.0 <= total) {
+            return;
+        }
+        if colour == colours {
+            self.best = Some((total, self.counts.clone()));
+            return;
+        }
+        let mut wanted = fit.iter().map(|&g| self.needs[g][colour]).collect_vec();
+        wanted.sort_unstable();
+        if colour + 1 == colours {
+            let count = wanted[self.n - 1];
+            self.pick(fit, total, count);
+            return;
+        }
+        wanted.dedup();
+        for count in wanted {
+            let fits = fit
+                .iter()
+                .filter(|&&g| self.needs[g][colour] <= count)
+                .count();
+            if fits >= self.n {
+                self.pick(fit, total, count);
+            }
+        }
+    }
+
+    fn pick(&mut self, fit: &[usize], total: u64, count: u32) {
+        let colour = self.counts.len();
+        let fit = fit
+            .iter()
+            .copied()
+            .filter(|&g| self.needs[g][colour] <= count)
+            .collect_vec();
+        self.counts.push(count);
+        self.run(&fit, total + u64::from(count));
+        self.counts.pop();
+    }
+}
+
+#[aoc(day2, part1)]
+pub(crate) fn part1(input: &[Game]) -> Result<u32> {
+    possible_ids(input, &Bag::default())
+}
+
+/// The sum of the ids of the games possible with `bag`.
+fn possible_ids(games: &[Game], bag: &Bag) -> Result<u32> {
+    games
+        .iter()
+        .filter(|g| g.is_possible(bag))
+        .try_fold(0u32, |sum, g| sum.checked_add(g.index))
+        .ok_or_else(overflow)
+}
+
+#[aoc(day2, part2)]
+pub(crate) fn part2(input: &[Game]) -> Result<u32> {
+    input
+        .iter()
+        .try_fold(0u32, |sum, g| sum.checked_add(g.power()?))
+        .ok_or_else(overflow)
+}
+
+#[aoc_stream(day2, part1)]
+pub(crate) fn part1_stream(input: &mut dyn BufRead) -> Result<u32> {
+    possible_ids_stream(input, &Bag::default())
+}
+
+fn possible_ids_stream(input: &mut dyn BufRead, bag: &Bag) -> Result<u32> {
+    sum_games(input, |g| {
+        Some(if g.is_possible(bag) { g.index } else { 0 })
+    })
+}
+
+#[aoc_stream(day2, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<u32> {
+    sum_games(input, Game::power)
+}
+
+/// The sum of `value` over the games, which is `None` if it overflows.
+fn sum_games(input: &mut dyn BufRead, value: impl Fn(&Game) -> Option<u32>) -> Result<u32> {
+    let mut sum = 0u32;
+    stream::lines(input, |l| {
+        let game = l.parse()?;
+        sum = value(&game)
+            .and_then(|v| sum.checked_add(v))
+            .ok_or_else(overflow)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// The sum of the ids of the games possible with 12 red, 13 green and 14 blue cubes.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day2};
+///
+/// let games = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+///              Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green";
+/// assert_eq!(day2::solve_part1(games)?, Answer::Unsigned(1));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&generator2(input)?))
+}
+
+/// The sum of the ids of the games possible with `bag`.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day2::{self, Bag}};
+///
+/// let games = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+///              Game 2: 2 purple, 3 blue";
+/// let bag: Bag = "purple=2,blue=3".parse()?;
+/// assert_eq!(day2::solve_part1_with(games, &bag)?, Answer::Unsigned(2));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1_with(input: &str, bag: &Bag) -> Result<Answer> {
+    solve(input, |input| possible_ids(&generator2(input)?, bag))
+}
+
+/// [`solve_part1_with`] in a single pass over `input`.
+pub fn stream_part1_with(input: &mut dyn BufRead, bag: &Bag) -> Result<Answer> {
+    Ok(Answer::from(possible_ids_stream(input, bag)?))
+}
+
+/// The sum of the powers of the fewest cubes of each colour that make each game possible:
+/// the product of the fewest red, green and blue cubes, and of any other colour drawn.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day2};
+///
+/// let games = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+///              Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green";
+/// assert_eq!(day2::solve_part2(games)?, Answer::Unsigned(4 * 2 * 6 + 20 * 13 * 6));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&generator2(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generator2, part1, part2, Bag};
+    use eyre::Result;
+
+    const DATA: &str = include_str!("../../input/2023/day2-ex1.txt");
+    const INPUT: &str = include_str!("../../input/2023/day2.txt");
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for line in DATA.lines().chain(INPUT.lines()) {
+            crate::parse::assert_round_trip::<super::Game>(line)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(&generator2(DATA)?)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(&generator2(DATA)?)?, 2286);
+        Ok(())
+    }
+
+    #[test]
+    fn colours() -> Result<()> {
+        let games = generator2("Game 1: 2 purple, 3 blue; 1 red, 1 green\nGame 2: 1 red; 5 blue")?;
+        assert_eq!(part1(&games)?, 2);
+        let bag = "purple=2,red=1,green=1,blue=3".parse::<Bag>()?;
+        assert_eq!(super::possible_ids(&games, &bag)?, 1);
+        assert_eq!(
+            games[0].fewest().to_string(),
+            "purple=2,blue=3,red=1,green=1"
+        );
+        assert_eq!(games[0].power(), Some(2 * 3));
+        // Game 2 has no green, whatever the bag.
+        assert_eq!(games[1].power(), Some(0));
+        assert_eq!(part2(&games)?, 2 * 3);
+        let mut input = "Game 1: 2 purple\nGame 2: 2 red".as_bytes();
+        let purple = "purple=2".parse()?;
+        assert_eq!(super::stream_part1_with(&mut input, &purple)?, 1.into());
+        Ok(())
+    }
+
+    #[test]
+    fn queries() -> Result<()> {
+        let games = super::games(DATA)?;
+        let bag = Bag::default();
+        let ids = super::possible(&games, &bag)
+            .map(|g| g.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [1, 2, 5]);
+        let why = games[3].impossible_draw(&bag).unwrap();
+        assert_eq!(
+            (why.round, why.draw.to_string()),
+            (2, "15 blue".to_string())
+        );
+        let why = games[2].impossible_draw(&"red=20,green=8,blue=6".parse()?);
+        assert_eq!(
+            why.map(|w| (w.round, w.draw.to_string())),
+            Some((1, "13 green".into()))
+        );
+        assert_eq!(games[0].impossible_draw(&bag), None);
+
+        let smallest = |n| super::smallest_bag(&games, n).map(|b| b.to_string());
+        assert_eq!(smallest(0).as_deref(), Some(""));
+        assert_eq!(smallest(1).as_deref(), Some("blue=4,red=1,green=3"));
+        assert_eq!(smallest(5).as_deref(), Some("blue=15,red=20,green=13"));
+        assert_eq!(smallest(6), None);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_bag() {
+        let e = |s: &str| format!("{:#}", s.parse::<Bag>().unwrap_err());
+        assert_eq!(
+            e("red=12,green"),
+            "bad bag entry `green`, expected `<colour>=<count>`"
+        );
+        assert_eq!(e("red=1,red=2"), "`red` is in the bag twice");
+        assert_eq!(
+            e("red=-1"),
+            "bad count `-1` for `red`: invalid digit found in string"
+        );
+        assert_eq!(e("=1"), "bad colour ``");
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        assert_eq!(super::part1_stream(&mut DATA.as_bytes())?, 8);
+        assert_eq!(super::part2_stream(&mut DATA.as_bytes())?, 2286);
+        let games = generator2(INPUT)?;
+        assert_eq!(super::part1_stream(&mut INPUT.as_bytes())?, part1(&games)?);
+        assert_eq!(super::part2_stream(&mut INPUT.as_bytes())?, part2(&games)?);
+        let e = super::part1_stream(&mut "Game 1: 3 blue\nGame 2: 4 dark blue".as_bytes());
+        assert_eq!(
+            format!("{:#}", e.unwrap_err()),
+            "line 2, column 9: bad draw \"4 dark blue\": expected a colour"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = generator2("Game 1: 3 blue\nGame 2: 3 blue, 4").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 17: bad draw \"4\": expected `<count> <colour>`"
+        );
+        let e = generator2("Game 1: 3 blue; x red").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 17: bad draw \"x red\": invalid digit found in string"
+        );
+        let e = generator2("Game x: 3 blue").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 6: invalid digit found in string"
+        );
+        let e = generator2("Game 1 3 blue").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: expected `Game <n>: <draws>`");
+        let e = part2(&generator2("Game 1: 2000 red, 2000 green, 2000 blue").unwrap());
+        assert_eq!(e.unwrap_err().to_string(), "overflow");
+    }
+}
@@ -0,0 +1,326 @@
+//! Day 3: Gear Ratios. Part numbers in an engine schematic.
+
+use crate::answer::{solve, Answer};
+use crate::grid::{Grid, Pos, UPos};
+use crate::parse::{error_at_position, overflow};
+use crate::render::{Picture, Rgb};
+use aoc_macros::{aoc, aoc_visual};
+use eyre::Result;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+struct Schematic {
+    grid: Grid<char>,
+}
+
+impl Schematic {
+    fn new(input: &str) -> Result<Self> {
+        let grid = input.parse()?;
+        Ok(Self { grid })
+    }
+
+    fn numbers(&self) -> NumberIter<'_> {
+        NumberIter {
+            sch: self,
+            line: 0,
+            pos: 0,
+        }
+    }
+
+    fn lines(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Cell at 1-based `line` and `pos`.
+    fn at(&self, line: usize, pos: usize) -> Option<Entry> {
+        let p = Pos::new(pos as isize - 1, line as isize - 1);
+        self.grid.get(p).map(|&c| Entry { c, line, pos })
+    }
+
+    fn is_symbol_adjacent(&self, num: Number) -> bool {
+        self.adjacent(num)
+            .iter()
+            .any(|e| e.c != '.' && !e.c.is_ascii_digit())
+    }
+
+    fn adjacent(&self, num: Number) -> Vec<Entry> {
+        let mut list = vec![];
+
+        let (top, mid, bottom) = (num.line - 1, num.line, num.line + 1);
+        let (left, right) = (num.pos - 1, num.pos + num.width);
+        list.extend(self.at(mid, left));
+        list.extend(self.at(mid, right));
+        for x in left..=right {
+            list.extend(self.at(top, x));
+            list.extend(self.at(bottom, x));
+        }
+
+        list
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Entry {
+    c: char,
+    line: usize,
+    pos: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Number {
+    value: u32,
+    line: usize,
+    pos: usize,
+    width: usize,
+}
+
+struct NumberIter<'a> {
+    sch: &'a Schematic,
+    line: usize,
+    pos: usize,
+}
+
+impl<'a> Iterator for NumberIter<'a> {
+    type Item = Result<Number>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.line < self.sch.lines() {
+            while self.pos < self.sch.grid.width() {
+                let s = &self.sch.grid.row(self.line)[self.pos..];
+                if s[0].is_ascii_digit() {
+                    let chs = s
+                        .iter()
+                        .take_while(|c| c.is_ascii_digit())
+                        .cloned()
+                        .collect_vec();
+                    let line = self.line + 1;
+                    let pos = self.pos + 1;
+                    let width = chs.len();
+                    self.pos += width;
+                    let value = match chs.iter().collect::<String>().parse() {
+                        Ok(value) => value,
+                        Err(e) => return Some(Err(error_at_position(line, pos, e))),
+                    };
+                    return Some(Ok(Number {
+                        value,
+                        line,
+                        pos,
+                        width,
+                    }));
+                } else {
+                    self.pos += 1;
+                }
+            }
+            self.pos = 0;
+            self.line += 1;
+        }
+        None
+    }
+}
+
+#[aoc(day3, part1)]
+pub(crate) fn part1(input: &str) -> Result<u32> {
+    let sch = Schematic::new(input)?;
+    let mut sum: u32 = 0;
+    for n in sch.numbers() {
+        let n = n?;
+        if sch.is_symbol_adjacent(n) {
+            sum = sum.checked_add(n.value).ok_or_else(overflow)?;
+        }
+    }
+    Ok(sum)
+}
+
+#[aoc(day3, part2)]
+pub(crate) fn part2(input: &str) -> Result<u32> {
+    let sch = Schematic::new(input)?;
+
+    let mut h: HashMap<_, Vec<_>> = HashMap::new();
+
+    for num in sch.numbers() {
+        let num = num?;
+        for e in sch.adjacent(num) {
+            if e.c == '*' {
+                h.entry(e).or_default().push(num);
+            }
+        }
+    }
+
+    let mut sum = 0u32;
+    for nums in h.values() {
+        if nums.len() == 2 {
+            let ratio = nums[0].value.checked_mul(nums[1].value);
+            sum = ratio
+                .and_then(|r| sum.checked_add(r))
+                .ok_or_else(overflow)?;
+        }
+    }
+    Ok(sum)
+}
+
+/// Part numbers in green and other numbers in red, symbols in purple and gears, stars
+/// next to exactly two numbers, in yellow.
+#[aoc_visual(day3)]
+pub(crate) fn visualize(input: &str) -> Result<Picture> {
+    let sch = Schematic::new(input)?;
+    let mut pic = Picture::new(&sch.grid);
+    for (p, &c) in sch.grid.cells() {
+        if c != '.' && !c.is_ascii_digit() {
+            pic.paint(p, Rgb::PURPLE);
+        }
+    }
+    let mut stars: HashMap<_, usize> = HashMap::new();
+    for num in sch.numbers() {
+        let num = num?;
+        let colour = if sch.is_symbol_adjacent(num) {
+            Rgb::GREEN
+        } else {
+            Rgb::RED
+        };
+        for x in num.pos..num.pos + num.width {
+            pic.paint(UPos::new(x - 1, num.line - 1), colour);
+        }
+        for e in sch.adjacent(num) {
+            if e.c == '*' {
+                *stars.entry(e).or_default() += 1;
+            }
+        }
+    }
+    for (e, _) in stars.into_iter().filter(|&(_, n)| n == 2) {
+        pic.paint(UPos::new(e.pos - 1, e.line - 1), Rgb::YELLOW);
+    }
+    pic.legend(Rgb::GREEN, "part number");
+    pic.legend(Rgb::RED, "not a part number");
+    pic.legend(Rgb::PURPLE, "symbol");
+    pic.legend(Rgb::YELLOW, "gear");
+    Ok(pic)
+}
+
+/// The sum of the numbers next to a symbol.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day3};
+///
+/// let schematic = "467..114..\n...*......\n..35..633.";
+/// assert_eq!(day3::solve_part1(schematic)?, Answer::Unsigned(467 + 35));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, part1)
+}
+
+/// The sum of the gear ratios, the products of the two numbers next to a `*`.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day3};
+///
+/// let schematic = "467..114..\n...*......\n..35..633.";
+/// assert_eq!(day3::solve_part2(schematic)?, Answer::Unsigned(467 * 35));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Number, Schematic};
+    use eyre::Result;
+
+    const DATA: &str = include_str!("../../input/2023/day3-ex1.txt");
+
+    #[test]
+    fn visualize() -> Result<()> {
+        use crate::render::Rgb;
+        let key = [
+            (Rgb::GREEN, 'p'),
+            (Rgb::RED, 'n'),
+            (Rgb::PURPLE, 's'),
+            (Rgb::YELLOW, 'g'),
+        ];
+        let pic = super::visualize(DATA)?;
+        let expected = "ppp..nnn..\n...g......\n..pp..ppp.\n......s...\nppps......\n\
+                        .....s.nn.\n..ppp.....\n......ppp.\n...s.g....\n.ppp.ppp..";
+        assert_eq!(pic.sketch(&key), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> Result<()> {
+        let d: &str = "467..114..\n...*......\n..35..633.";
+        let reference = [
+            Number {
+                value: 467,
+                line: 1,
+                pos: 1,
+                width: 3,
+            },
+            Number {
+                value: 114,
+                line: 1,
+                pos: 6,
+                width: 3,
+            },
+            Number {
+                value: 35,
+                line: 3,
+                pos: 3,
+                width: 2,
+            },
+            Number {
+                value: 633,
+                line: 3,
+                pos: 7,
+                width: 3,
+            },
+        ];
+
+        let numbers = Schematic::new(d)?.numbers().collect::<Result<Vec<_>>>()?;
+        itertools::assert_equal(numbers, reference);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_test() -> Result<()> {
+        assert_eq!(super::part1(DATA)?, 4361);
+        Ok(())
+    }
+
+    #[test]
+    fn test_adjacent() -> Result<()> {
+        let sch = Schematic::new(DATA)?;
+
+        let n = Number {
+            value: 617,
+            line: 5,
+            pos: 1,
+            width: 3,
+        };
+
+        assert!(sch.is_symbol_adjacent(n));
+        Ok(())
+    }
+
+    #[test]
+    fn part2_test() -> Result<()> {
+        assert_eq!(super::part2(DATA)?, 467835);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = super::part1("467..\n...*").unwrap_err();
+        assert_eq!(e.to_string(), "line 2: expected 5 columns, found 4");
+        let e = super::part2("...*.\n.12345678901").unwrap_err();
+        assert_eq!(e.to_string(), "line 2: expected 5 columns, found 12");
+        let e = super::part1(".12345678901*").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 2: number too large to fit in target type"
+        );
+        let e = super::part1("4000000000*\n..400000000").unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+        let e = super::part2("100000*100000").unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+    }
+}
@@ -0,0 +1,264 @@
+//! Day 4: Scratchcards.
+
+use std::io::BufRead;
+use std::{collections::VecDeque, str::FromStr};
+
+use crate::answer::{solve, Answer};
+use crate::parse::{self, error_at, on_line, overflow};
+use crate::stream;
+use aoc_macros::{aoc, aoc_generator, aoc_stream};
+use eyre::{bail, eyre, Result};
+use itertools::Itertools;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Card {
+    id: u32,
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+impl Card {
+    fn matches(&self) -> u32 {
+        let winning = self.left.iter().sorted_unstable().cloned().collect_vec();
+        let mut matches = 0;
+        for &mine in &self.right {
+            if winning.binary_search(&mine).is_ok() {
+                matches += 1;
+            }
+        }
+        matches
+    }
+}
+
+/// Parses the numbers in `s`, a part of `line`.
+fn parse_list(line: &str, s: &str) -> Result<Vec<u32>> {
+    s.split_ascii_whitespace()
+        .map(|n| n.parse::<u32>().map_err(|e| error_at(line, n, e)))
+        .collect()
+}
+
+impl FromStr for Card {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pre, lists) = s
+            .split_once(':')
+            .ok_or_else(|| eyre!("expected `Card <n>: <numbers> | <numbers>`"))?;
+        let id = pre
+            .strip_prefix("Card")
+            .ok_or_else(|| error_at(s, pre, "expected `Card <n>`"))?
+            .trim();
+        let id = id.parse::<u32>().map_err(|e| error_at(s, id, e))?;
+
+        let (left, right) = lists
+            .split_once('|')
+            .ok_or_else(|| error_at(s, lists, "expected `<numbers> | <numbers>`"))?;
+        let left = parse_list(s, left)?;
+        let right = parse_list(s, right)?;
+
+        Ok(Card { id, left, right })
+    }
+}
+
+impl std::fmt::Display for Card {
+    /// Numbers are right-aligned in two columns. A width pads the id, as inputs align ids
+    /// to the widest one, e.g. `{card:3}` shows `Card   1: ...`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |l: &[u32]| l.iter().map(|n| format!("{n:>2}")).join(" ");
+        let width = f.width().unwrap_or(0);
+        write!(
+            f,
+            "Card {:>width$}: {} | {}",
+            self.id,
+            list(&self.left),
+            list(&self.right)
+        )
+    }
+}
+
+#[aoc_generator(day4)]
+pub(crate) fn generator(input: &str) -> Result<Vec<Card>> {
+    parse::lines(input, str::parse)
+}
+
+#[aoc(day4, part1)]
+pub(crate) fn part1(cards: &[Card]) -> Result<u32> {
+    cards.iter().try_fold(0u32, |sum, c| {
+        sum.checked_add(points(c.matches())?).ok_or_else(overflow)
+    })
+}
+
+/// The points of a card with `m` matches.
+fn points(m: u32) -> Result<u32> {
+    match m {
+        0 => Ok(0),
+        m => 1u32
+            .checked_shl(m - 1)
+            .ok_or_else(|| eyre!("{m} matches is too many points")),
+    }
+}
+
+#[aoc_stream(day4, part1)]
+pub(crate) fn part1_stream(input: &mut dyn BufRead) -> Result<u32> {
+    let mut sum = 0u32;
+    stream::lines(input, |l| {
+        let points = points(l.parse::<Card>()?.matches())?;
+        sum = sum.checked_add(points).ok_or_else(overflow)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+#[aoc(day4, part2)]
+pub(crate) fn part2(cards: &[Card]) -> Result<usize> {
+    let mut copies = Copies::default();
+    for (i, c) in cards.iter().enumerate() {
+        copies.add(c).map_err(|e| on_line(e, i + 1))?;
+    }
+    Ok(copies.total)
+}
+
+#[aoc_stream(day4, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<usize> {
+    let mut copies = Copies::default();
+    stream::lines(input, |l| copies.add(&l.parse()?))?;
+    Ok(copies.total)
+}
+
+/// Counts the copies of cards won so far. Only the copies of the next cards that a card
+/// can win are held, so memory is bounded by the number of matches.
+#[derive(Default)]
+struct Copies {
+    /// Copies of the next cards, won by the previous ones.
+    next: VecDeque<usize>,
+    total: usize,
+    cards: u32,
+}
+
+impl Copies {
+    /// Adds a card, which must be the next one in order.
+    fn add(&mut self, c: &Card) -> Result<()> {
+        self.cards += 1;
+        if c.id != self.cards {
+            bail!("expected card {}, found card {}", self.cards, c.id);
+        }
+        let count = self.next.pop_front().unwrap_or(0) + 1;
+        self.total = self.total.checked_add(count).ok_or_else(overflow)?;
+        for i in 0..c.matches() as usize {
+            match self.next.get_mut(i) {
+                Some(cnt) => *cnt = cnt.checked_add(count).ok_or_else(overflow)?,
+                None => self.next.push_back(count),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The sum of the points of the cards, doubling for every match after the first.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day4};
+///
+/// let cards = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+///              Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
+/// assert_eq!(day4::solve_part1(cards)?, Answer::Unsigned(8 + 2));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&generator(input)?))
+}
+
+/// The number of cards held once every card has won copies of the cards after it.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day4};
+///
+/// let cards = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+///              Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
+/// assert_eq!(day4::solve_part2(cards)?, Answer::Unsigned(1 + 2));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&generator(input)?))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{generator, part1, part2};
+    use eyre::Result;
+    use itertools::Itertools;
+
+    const DATA: &str = include_str!("../../input/2023/day4-ex1.txt");
+    const INPUT: &str = include_str!("../../input/2023/day4.txt");
+
+    #[test]
+    fn p1() -> Result<()> {
+        assert_eq!(part1(&generator(DATA)?)?, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn p2() -> Result<()> {
+        assert_eq!(part2(&generator(DATA)?)?, 30);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for input in [DATA, INPUT] {
+            let cards = generator(input)?;
+            let width = cards.last().map_or(0, |c| c.id.to_string().len());
+            let shown = cards.iter().map(|c| format!("{c:width$}")).join("\n");
+            assert_eq!(shown, input.trim_end());
+            assert_eq!(generator(&shown)?, cards);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        assert_eq!(super::part1_stream(&mut DATA.as_bytes())?, 13);
+        assert_eq!(super::part2_stream(&mut DATA.as_bytes())?, 30);
+        let cards = generator(INPUT)?;
+        assert_eq!(super::part1_stream(&mut INPUT.as_bytes())?, part1(&cards)?);
+        assert_eq!(super::part2_stream(&mut INPUT.as_bytes())?, part2(&cards)?);
+        let input = "Card 1: 41 | 83\nCard 3: 1 | 2";
+        let e = super::part2_stream(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: expected card 2, found card 3");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() -> Result<()> {
+        let e = generator("Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 12: invalid digit found in string"
+        );
+        let e = generator("Card 1: 41 48 83 86").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 8: expected `<numbers> | <numbers>`"
+        );
+        let e = generator("Crad 1: 41 | 83").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1, column 1: expected `Card <n>`");
+        let e = part2(&generator("Card 1: 41 | 83\nCard 3: 1 | 2")?).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: expected card 2, found card 3");
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> Result<()> {
+        // Five cards of 2^30 points each.
+        let numbers = (1..=31).join(" ");
+        let input = (1..=5)
+            .map(|i| format!("Card {i}: {numbers} | {numbers}"))
+            .join("\n");
+        let e = part1(&generator(&input)?).unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+        let e = super::part1_stream(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 4: overflow");
+        Ok(())
+    }
+}
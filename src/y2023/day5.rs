@@ -0,0 +1,462 @@
+//! Day 5: If You Give A Seed A Fertilizer. Seeds mapped through a chain of [`Map`]s.
+
+use std::ops::Range;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{error_at, on_line, overflow};
+use aoc_macros::{aoc, aoc_generator};
+use eyre::{bail, eyre, Result};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+
+/// An almanac: the seeds, and the maps that take them to soil, fertilizer and so on to a
+/// location. Parses and prints as the puzzle input.
+///
+/// ```
+/// use aoc_2023::y2023::day5::{Input, Ranges};
+///
+/// let almanac: Input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48".parse()?;
+/// assert_eq!(almanac.location(79), 81);
+/// let seeds = Ranges::new(almanac.seeds())?;
+/// assert_eq!(almanac.locations(&seeds).as_slice(), [57..70, 81..95]);
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Input {
+    seeds: Vec<usize>,
+    maps: Vec<Map>,
+}
+
+/// One step of the almanac, e.g. `seed-to-soil`. Values outside its entries map to
+/// themselves.
+///
+/// Entries in input order. Their sources are disjoint, so the order doesn't matter.
+#[derive(Debug, Default, PartialEq)]
+pub struct Map {
+    name: String,
+    data: Vec<MapEntry>,
+}
+
+impl Map {
+    /// The name before ` map:`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Where `x` goes.
+    pub fn apply(&self, x: usize) -> usize {
+        for m in &self.data {
+            if m.source <= x && (x - m.source) < m.count {
+                let new_value = x - m.source + m.dest;
+                return new_value;
+            }
+        }
+        x
+    }
+
+    /// Where every value in `x` goes, without mapping them one by one.
+    pub fn apply_ranges(&self, x: &Ranges) -> Ranges {
+        let mut from = x.clone();
+        let mut to = vec![];
+        for m in &self.data {
+            let mut rest = Ranges(vec![]);
+            for r in from.0 {
+                let (mapped, unmapped) = m.map_range(r);
+                to.extend(mapped);
+                rest.0.extend(unmapped);
+            }
+            from = rest;
+            from.sort_and_merge();
+        }
+        from.0.extend(to);
+        from.sort_and_merge();
+        from
+    }
+}
+
+impl Map {
+    fn new(name: String) -> Self {
+        Self { name, data: vec![] }
+    }
+}
+
+/// `<dest> <source> <count>` on line `n`. Neither range may overflow.
+fn parse_entry(n: usize, l: &str) -> Result<MapEntry> {
+    let entry: MapEntry = l
+        .parse()
+        .map_err(|_| eyre!("line {n}: expected `<dest> <source> <count>`"))?;
+    let end = |start: usize| start.checked_add(entry.count);
+    if end(entry.source).is_none() || end(entry.dest).is_none() {
+        bail!("line {n}: range overflows");
+    }
+    Ok(entry)
+}
+
+impl std::str::FromStr for Map {
+    type Err = eyre::Report;
+
+    /// `<name> map:` and its entries, with line numbers relative to `s`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(n, l)| (n + 1, l));
+        let (_, first) = lines.next().ok_or_else(|| eyre!("empty map"))?;
+        let name = first
+            .strip_suffix(" map:")
+            .ok_or_else(|| eyre!("line 1: expected `<name> map:`"))?;
+        let mut map = Map::new(name.to_string());
+        for (n, l) in lines {
+            map.data.push(parse_entry(n, l)?);
+        }
+        Ok(map)
+    }
+}
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} map:", self.name)?;
+        self.data.iter().try_for_each(|e| write!(f, "\n{e}"))
+    }
+}
+
+#[derive(Display, FromStr, PartialEq, Debug)]
+#[display("{dest} {source} {count}")]
+pub(crate) struct MapEntry {
+    source: usize,
+    dest: usize,
+    count: usize,
+}
+
+impl MapEntry {
+    #[allow(clippy::single_range_in_vec_init)]
+    fn map_range(&self, r: Range<usize>) -> (Option<Range<usize>>, Vec<Range<usize>>) {
+        // Cases:
+        //     1          2         3         4
+        //   | r |       | r |   | r |    |   r   |
+        // |   m   |   | m |       | m |    | m |
+
+        let (left, right) = (self.source, self.source + self.count);
+
+        // No overlap
+        if right <= r.start || left >= r.end {
+            return (None, vec![r]);
+        }
+
+        // Case 1
+        if left <= r.start && right >= r.end {
+            let from = r.start - self.source + self.dest;
+            let to = r.end - self.source + self.dest;
+            return (Some(from..to), vec![]);
+        }
+
+        // Case 2
+        if left <= r.start && right < r.end {
+            let from = r.start - self.source + self.dest;
+            let to = right - self.source + self.dest;
+            return (Some(from..to), vec![right..r.end]);
+        }
+
+        // Case 3
+        if left > r.start && right >= r.end {
+            let from = left - self.source + self.dest;
+            let to = r.end - self.source + self.dest;
+            return (Some(from..to), vec![r.start..left]);
+        }
+
+        // Case 1
+        if left > r.start && right < r.end {
+            let from = left - self.source + self.dest;
+            let to = right - self.source + self.dest;
+            return (Some(from..to), vec![r.start..left, right..r.end]);
+        }
+
+        (None, vec![])
+    }
+}
+
+/// A set of values as half-open ranges.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ranges(Vec<Range<usize>>);
+
+impl Ranges {
+    /// Ranges from pairs of start and length, as the seeds of part 2 are given. Fails on an
+    /// odd number of values or a range past `usize::MAX`.
+    pub fn new(list: &[usize]) -> Result<Self> {
+        if !list.len().is_multiple_of(2) {
+            bail!(
+                "expected pairs of seed start and length, found {} values",
+                list.len()
+            );
+        }
+        let ranges = list
+            .iter()
+            .tuples()
+            .map(|(&s, &n)| {
+                let end = s.checked_add(n);
+                end.map(|end| s..end)
+                    .ok_or_else(|| eyre!("seed range {s} {n} overflows"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self(ranges))
+    }
+
+    fn sort_and_merge(&mut self) {
+        self.0.sort_by_key(|r| r.start);
+        let v = self
+            .0
+            .iter()
+            .cloned()
+            .coalesce(|prev, curr| {
+                if curr.start <= prev.end {
+                    Ok(prev.start..(prev.end.max(curr.end)))
+                } else {
+                    Err((prev, curr))
+                }
+            })
+            .collect();
+        self.0 = v;
+    }
+
+    /// The lowest value, if any.
+    pub fn min(&self) -> Option<usize> {
+        self.0.iter().map(|r| r.start).min()
+    }
+
+    /// The ranges, sorted and merged if they came from [`Map::apply_ranges`], otherwise as
+    /// given.
+    pub fn as_slice(&self) -> &[Range<usize>] {
+        &self.0
+    }
+}
+
+impl Input {
+    /// The seeds as listed. Part 2 reads them as pairs, see [`Ranges::new`].
+    pub fn seeds(&self) -> &[usize] {
+        &self.seeds
+    }
+
+    /// The maps, in the order they apply.
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
+    }
+
+    /// Where `seed` ends up after every map.
+    pub fn location(&self, seed: usize) -> usize {
+        self.maps.iter().fold(seed, |s, m| m.apply(s))
+    }
+
+    /// Where `seeds` end up after every map.
+    pub fn locations(&self, seeds: &Ranges) -> Ranges {
+        let mut ranges = seeds.clone();
+        for m in &self.maps {
+            ranges = m.apply_ranges(&ranges);
+        }
+        ranges
+    }
+}
+
+impl std::str::FromStr for Input {
+    type Err = eyre::Report;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut i = input.lines().enumerate().map(|(n, l)| (n + 1, l));
+        let (_, first) = i.next().ok_or_else(|| eyre!("empty input"))?;
+        let seeds = first
+            .strip_prefix("seeds:")
+            .ok_or_else(|| eyre!("line 1: expected `seeds:`"))?;
+        let seeds = seeds
+            .split_ascii_whitespace()
+            .map(|s| s.parse().map_err(|e| error_at(first, s, e)))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| on_line(e, 1))?;
+
+        let mut maps = vec![];
+
+        let mut map = None::<Map>;
+        for (n, l) in i {
+            if l.is_empty() {
+                maps.extend(map.take());
+                continue;
+            }
+
+            if let Some(s) = l.strip_suffix(" map:") {
+                maps.extend(map.replace(Map::new(s.to_string())));
+            } else {
+                let m = map
+                    .as_mut()
+                    .ok_or_else(|| eyre!("line {n}: expected `<name> map:`"))?;
+                m.data.push(parse_entry(n, l)?);
+            }
+        }
+
+        maps.extend(map);
+        Ok(Input { seeds, maps })
+    }
+}
+
+impl std::fmt::Display for Input {
+    /// Maps are separated by blank lines.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "seeds:")?;
+        self.seeds.iter().try_for_each(|s| write!(f, " {s}"))?;
+        self.maps.iter().try_for_each(|m| write!(f, "\n\n{m}"))
+    }
+}
+
+#[aoc_generator(day5)]
+pub(crate) fn generator(input: &str) -> Result<Input> {
+    input.parse()
+}
+
+#[aoc(day5, part1)]
+pub(crate) fn part1(input: &Input) -> Result<usize> {
+    input
+        .seeds
+        .iter()
+        .map(|&s| input.location(s))
+        .min()
+        .ok_or_else(|| eyre!("no seeds"))
+}
+
+#[aoc(day5, part2)]
+pub(crate) fn part2(input: &Input) -> Result<usize> {
+    let seeds = Ranges::new(&input.seeds)?;
+    input
+        .locations(&seeds)
+        .min()
+        .ok_or_else(|| eyre!("no seeds"))
+}
+
+/// Seeds the naive solver tries at most.
+const NAIVE_SEEDS: usize = 10_000_000;
+
+/// Maps every seed on its own.
+#[aoc(day5, part2, naive)]
+pub(crate) fn part2_naive(input: &Input) -> Result<usize> {
+    let ranges = Ranges::new(&input.seeds)?;
+    let count = ranges
+        .0
+        .iter()
+        .try_fold(0usize, |n, r| n.checked_add(r.len()))
+        .ok_or_else(overflow)?;
+    if count > NAIVE_SEEDS {
+        bail!("{count} seeds are too many to map one by one");
+    }
+    ranges
+        .0
+        .into_iter()
+        .flatten()
+        .map(|s| input.location(s))
+        .min()
+        .ok_or_else(|| eyre!("no seeds"))
+}
+
+/// The lowest location of any seed.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day5};
+///
+/// let almanac = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48";
+/// assert_eq!(day5::solve_part1(almanac)?, Answer::Unsigned(13));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&generator(input)?))
+}
+
+/// The lowest location of any seed, with the seeds read as [`Ranges`].
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day5};
+///
+/// let almanac = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48";
+/// assert_eq!(day5::solve_part2(almanac)?, Answer::Unsigned(57));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&generator(input)?))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{generator, part1, part2, Input, Map, Ranges};
+    use crate::parse::assert_round_trip;
+    use eyre::Result;
+
+    const DATA: &str = include_str!("../../input/2023/day5-ex1.txt");
+    const INPUT: &str = include_str!("../../input/2023/day5.txt");
+
+    #[test]
+    fn p1() -> Result<()> {
+        assert_eq!(part1(&generator(DATA)?)?, 35);
+        Ok(())
+    }
+
+    #[test]
+    fn p2() -> Result<()> {
+        assert_eq!(part2(&generator(DATA)?)?, 46);
+        assert_eq!(super::part2_naive(&generator(DATA)?)?, 46);
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for input in [DATA, INPUT] {
+            assert_round_trip::<Input>(input)?;
+        }
+        let map = "a-to-b map:\n50 98 2\n52 50 48";
+        assert_round_trip::<Map>(map)?;
+        // A header ends the previous map even without a blank line.
+        let input = generator("seeds: 1\n\na-to-b map:\n1 2 3\nb-to-c map:")?;
+        assert_eq!(
+            input.to_string(),
+            "seeds: 1\n\na-to-b map:\n1 2 3\n\nb-to-c map:"
+        );
+        let e = "a-to-b map:\n\n1 2 3".parse::<Map>().unwrap_err();
+        assert_eq!(e.to_string(), "line 2: expected `<dest> <source> <count>`");
+        Ok(())
+    }
+
+    #[test]
+    fn sort_and_merge_test() -> Result<()> {
+        let mut r = Ranges::new(&[10, 4, 11, 2, 3, 3, 6, 3])?;
+        let c = Ranges::new(&[3, 6, 10, 4])?;
+        r.sort_and_merge();
+        assert_eq!(r, c);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() -> Result<()> {
+        let e = generator("seed: 1 2").unwrap_err();
+        assert_eq!(e.to_string(), "line 1: expected `seeds:`");
+        let e = generator("seeds: 1 x").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 10: invalid digit found in string"
+        );
+        let e = generator("seeds: 1\n\n1 2 3").unwrap_err();
+        assert_eq!(e.to_string(), "line 3: expected `<name> map:`");
+        let e = generator("seeds: 1\n\na-to-b map:\n1 2").unwrap_err();
+        assert_eq!(e.to_string(), "line 4: expected `<dest> <source> <count>`");
+        let e = generator("seeds: 1\n\na-to-b map:\n0 1 18446744073709551615").unwrap_err();
+        assert_eq!(e.to_string(), "line 4: range overflows");
+        let e = part2(&generator("seeds: 2 18446744073709551615")?).unwrap_err();
+        assert_eq!(e.to_string(), "seed range 2 18446744073709551615 overflows");
+        let e = part2(&generator("seeds: 1 2 3")?).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "expected pairs of seed start and length, found 3 values"
+        );
+        let e = super::part2_naive(&generator("seeds: 1 20000000")?).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "20000000 seeds are too many to map one by one"
+        );
+        let full = format!("seeds: 0 {} 0 {}", usize::MAX, usize::MAX);
+        let e = super::part2_naive(&generator(&full)?).unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+        let e = part1(&generator("seeds:")?).unwrap_err();
+        assert_eq!(e.to_string(), "no seeds");
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+//! Day 6: Wait For It. Boat races won by holding the button long enough.
+
+use std::io::BufRead;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{error_at, on_line, overflow};
+use crate::stream;
+use aoc_macros::{aoc, aoc_stream};
+use eyre::{bail, eyre, Result, WrapErr};
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Input1 {
+    time: Vec<u64>,
+    dist: Vec<u64>,
+}
+
+impl std::str::FromStr for Input1 {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> std::prelude::v1::Result<Self, Self::Err> {
+        let (time, dist) = split_lines(s)?;
+        let parse = |n: usize, line: &str, prefix: &str| -> Result<Vec<u64>> {
+            line[prefix.len()..]
+                .split_ascii_whitespace()
+                .map(|s| s.parse().map_err(|e| error_at(line, s, e)))
+                .collect::<Result<_>>()
+                .map_err(|e| on_line(e, n))
+        };
+        let time = parse(1, time, "Time:")?;
+        let dist = parse(2, dist, "Distance:")?;
+        if time.len() != dist.len() {
+            bail!("line 2: {} times but {} distances", time.len(), dist.len());
+        }
+        Ok(Input1 { time, dist })
+    }
+}
+
+impl std::fmt::Display for Input1 {
+    /// Numbers are right-aligned in columns two spaces apart, or as many as a width gives,
+    /// e.g. `{input:3}` as in real inputs.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gap = f.width().unwrap_or(2);
+        let widths: Vec<usize> = self
+            .time
+            .iter()
+            .zip(&self.dist)
+            .map(|(t, d)| t.to_string().len().max(d.to_string().len()))
+            .collect();
+        for (label, values, end) in [("Time:", &self.time, "\n"), ("Distance:", &self.dist, "")] {
+            // Labels are padded so that the columns line up.
+            let mut pad = "Distance:".len() - label.len() + gap;
+            write!(f, "{label}")?;
+            for (n, width) in values.iter().zip(&widths) {
+                write!(f, "{:pad$}{n:>width$}", "")?;
+                pad = gap;
+            }
+            write!(f, "{end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The `Time:` and `Distance:` lines, checked for their prefixes.
+fn split_lines(s: &str) -> Result<(&str, &str)> {
+    let mut l = s.lines();
+    let time = l.next().ok_or_else(|| eyre!("line 1: missing"))?;
+    if !time.starts_with("Time:") {
+        bail!("line 1: expected `Time:`");
+    }
+    let dist = l.next().ok_or_else(|| eyre!("line 2: missing"))?;
+    if !dist.starts_with("Distance:") {
+        bail!("line 2: expected `Distance:`");
+    }
+    Ok((time, dist))
+}
+
+#[aoc(day6, part1)]
+pub(crate) fn part1(input: &str) -> Result<u64> {
+    product_of_margins(input, |time, dist| Ok(margin(time, dist)))
+}
+
+#[aoc(day6, part1, naive)]
+pub(crate) fn part1_naive(input: &str) -> Result<u64> {
+    product_of_margins(input, margin_naive)
+}
+
+fn product_of_margins(input: &str, margin: fn(u64, u64) -> Result<u64>) -> Result<u64> {
+    let input = input.parse::<Input1>()?;
+    let times = input.time.iter().cloned();
+    let dists = input.dist.iter().cloned();
+    let mut pairs = times.zip(dists);
+
+    pairs.try_fold(1u64, |product, (time, dist)| {
+        product
+            .checked_mul(margin(time, dist)?)
+            .ok_or_else(overflow)
+    })
+}
+
+fn margin(time: u64, dist: u64) -> u64 {
+    let t: f64 = time as f64;
+    let d: f64 = dist as f64;
+
+    // Holding for t/2 is the best we can do. If that doesn't beat the record, nothing does.
+    if 4.0 * d >= t * t {
+        return 0;
+    }
+
+    let r = (t * t - 4.0 * d).sqrt();
+    let low = 0.5 * (t - r);
+    let high = 0.5 * (t + r);
+    let low = low.floor() as u64;
+    let high = high.ceil() as u64;
+
+    // Rounding may leave no whole holding time strictly between the roots.
+    (high - low).saturating_sub(1)
+}
+
+/// Race time the naive solver tries every holding time of at most.
+const NAIVE_TIME: u64 = 10_000_000;
+
+/// Tries every holding time.
+fn margin_naive(time: u64, dist: u64) -> Result<u64> {
+    if time > NAIVE_TIME {
+        bail!("race time {time} is too long to try every holding time");
+    }
+    (0..=time).try_fold(0u64, |count, hold| {
+        let d = hold.checked_mul(time - hold).ok_or_else(overflow)?;
+        Ok(count + u64::from(d > dist))
+    })
+}
+
+#[aoc(day6, part2)]
+pub(crate) fn part2(input: &str) -> Result<u64> {
+    let (time, dist) = single_race(input)?;
+    Ok(margin(time, dist))
+}
+
+#[aoc(day6, part2, naive)]
+pub(crate) fn part2_naive(input: &str) -> Result<u64> {
+    let (time, dist) = single_race(input)?;
+    margin_naive(time, dist)
+}
+
+/// The time and distance with the spaces between digits removed.
+fn single_race(input: &str) -> Result<(u64, u64)> {
+    let (time, dist) = split_lines(input)?;
+    let parse = |n: usize, line: &str, prefix: &str| -> Result<u64> {
+        line[prefix.len()..]
+            .split_ascii_whitespace()
+            .collect::<String>()
+            .parse()
+            .map_err(|e| eyre!("line {n}: {e}"))
+    };
+    let time = parse(1, time, "Time:")?;
+    let dist = parse(2, dist, "Distance:")?;
+    Ok((time, dist))
+}
+
+/// Holds the times, 8 bytes per race, as each is needed when its distance comes.
+#[aoc_stream(day6, part1)]
+pub(crate) fn part1_stream(input: &mut dyn BufRead) -> Result<u64> {
+    let mut times = vec![];
+    stream_line(input, 1, "Time:", |t| {
+        times.push(t.parse::<u64>()?);
+        Ok(())
+    })?;
+    let mut product = 1u64;
+    let mut dists = 0;
+    stream_line(input, 2, "Distance:", |d| {
+        let d = d.parse::<u64>()?;
+        if let Some(&t) = times.get(dists) {
+            product = product.checked_mul(margin(t, d)).ok_or_else(overflow)?;
+        }
+        dists += 1;
+        Ok(())
+    })?;
+    if times.len() != dists {
+        bail!("line 2: {} times but {} distances", times.len(), dists);
+    }
+    Ok(product)
+}
+
+#[aoc_stream(day6, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<u64> {
+    let mut race = [None, None];
+    for (n, (prefix, value)) in ["Time:", "Distance:"].iter().zip(&mut race).enumerate() {
+        stream_line(input, n + 1, prefix, |w| {
+            *value = Some(append_digits(value.unwrap_or(0), w)?);
+            Ok(())
+        })?;
+    }
+    let [time, dist] = race;
+    let time = time.ok_or_else(|| eyre!("line 1: expected a number"))?;
+    let dist = dist.ok_or_else(|| eyre!("line 2: expected a number"))?;
+    Ok(margin(time, dist))
+}
+
+/// `n` followed by the digits of `w`, one at a time so that neither leading zeros nor
+/// the length of `w` limit it.
+fn append_digits(n: u64, w: &str) -> Result<u64> {
+    w.bytes().try_fold(n, |n, b| {
+        if !b.is_ascii_digit() {
+            bail!("invalid digit found in string");
+        }
+        n.checked_mul(10)
+            .and_then(|n| n.checked_add(u64::from(b - b'0')))
+            .ok_or_else(overflow)
+    })
+}
+
+/// Calls `f` on the words after `prefix` on line `n`, the next one in `input`.
+fn stream_line(
+    input: &mut dyn BufRead,
+    n: usize,
+    prefix: &str,
+    mut f: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut first = true;
+    let found = stream::words(input, |w| {
+        let w = if first {
+            first = false;
+            w.strip_prefix(prefix)
+                .ok_or_else(|| eyre!("expected `{prefix}`"))?
+        } else {
+            w
+        };
+        if w.is_empty() {
+            return Ok(());
+        }
+        f(w)
+    })
+    .wrap_err_with(|| format!("line {n}"))?;
+    if !found {
+        bail!("line {n}: missing");
+    }
+    if first {
+        bail!("line {n}: expected `{prefix}`");
+    }
+    Ok(())
+}
+
+/// The product of the number of ways to win each race.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day6};
+///
+/// let races = "Time:      7  15   30\nDistance:  9  40  200";
+/// assert_eq!(day6::solve_part1(races)?, Answer::Unsigned(4 * 8 * 9));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, part1)
+}
+
+/// The number of ways to win the single race the digits make without their spaces.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day6};
+///
+/// let races = "Time:      7  15   30\nDistance:  9  40  200";
+/// assert_eq!(day6::solve_part2(races)?, Answer::Unsigned(71503));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, part2)
+}
+
+#[cfg(test)]
+mod test {
+    use eyre::Result;
+
+    const EXAMPLE: &str = include_str!("../../input/2023/day6-ex1.txt");
+
+    #[test]
+    fn gen_test() -> Result<()> {
+        let i: super::Input1 = EXAMPLE.parse()?;
+        assert_eq!(&i.time, &[7, 15, 30]);
+        assert_eq!(&i.dist, &[9, 40, 200]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        crate::parse::assert_round_trip::<super::Input1>(EXAMPLE)?;
+        let input = include_str!("../../input/2023/day6.txt");
+        let races: super::Input1 = input.parse()?;
+        assert_eq!(format!("{races:3}"), input.trim_end());
+        Ok(())
+    }
+
+    #[test]
+    fn part1() -> Result<()> {
+        let result = super::part1(EXAMPLE)?;
+        assert_eq!(result, 288);
+        Ok(())
+    }
+
+    #[test]
+    fn part2() -> Result<()> {
+        let result = super::part2(EXAMPLE)?;
+        assert_eq!(result, 71503);
+        Ok(())
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let input = include_str!("../../input/2023/day6.txt");
+        assert_eq!(super::part1_stream(&mut EXAMPLE.as_bytes())?, 288);
+        assert_eq!(super::part2_stream(&mut EXAMPLE.as_bytes())?, 71503);
+        assert_eq!(
+            super::part1_stream(&mut input.as_bytes())?,
+            super::part1(input)?
+        );
+        assert_eq!(
+            super::part2_stream(&mut input.as_bytes())?,
+            super::part2(input)?
+        );
+
+        let e = super::part1_stream(&mut "Time: 7 15\nDistance: 9".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "line 2: 2 times but 1 distances");
+        let e = super::part1_stream(&mut "Time: 7 15\nDistance: 9 x".as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: invalid digit found in string");
+        let e = super::part2_stream(&mut "Time: 7 15".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "line 2: missing");
+        let e = super::part2_stream(&mut "Tim: 7 15\nDistance: 9".as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: expected `Time:`");
+        let long = format!("Time: {}\nDistance: 9", "9 ".repeat(100));
+        let e = super::part2_stream(&mut long.as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: overflow");
+        let e = super::part2_stream(&mut "Time: 7 1-5\nDistance: 9".as_bytes()).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: invalid digit found in string");
+        let e = super::part2_stream(&mut "Time:\nDistance: 9".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "line 1: expected a number");
+        Ok(())
+    }
+
+    #[test]
+    fn leading_zeros() -> Result<()> {
+        let input = format!("Time: {}7\nDistance: 0 9", "0".repeat(30));
+        assert_eq!(super::part2_stream(&mut input.as_bytes())?, 4);
+        assert_eq!(super::part2(&input)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn unwinnable() {
+        assert_eq!(super::margin(4, 4), 0);
+        assert_eq!(super::margin(3, 5), 0);
+    }
+
+    #[test]
+    fn margin_matches_naive() {
+        for time in 0..60 {
+            for dist in 0..=time * time / 4 + 1 {
+                assert_eq!(
+                    super::margin(time, dist),
+                    super::margin_naive(time, dist).unwrap(),
+                    "time {time}, distance {dist}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn naive_gives_up() {
+        let e = super::margin_naive(u64::MAX, 0).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!(
+                "race time {} is too long to try every holding time",
+                u64::MAX
+            )
+        );
+        let e = super::part2_naive("Time: 1 0000000 0\nDistance: 9").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "race time 100000000 is too long to try every holding time"
+        );
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = super::part1("Time: 7 15\nDistance: 9 x").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 13: invalid digit found in string"
+        );
+        let e = super::part1("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(e.to_string(), "line 2: 2 times but 1 distances");
+        let e = super::part2("Time: 7 15").unwrap_err();
+        assert_eq!(e.to_string(), "line 2: missing");
+        let e = super::part2("Tim: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(e.to_string(), "line 1: expected `Time:`");
+        let e = super::part2("Time: 7 1-5\nDistance: 9").unwrap_err();
+        assert_eq!(e.to_string(), "line 1: invalid digit found in string");
+        let e = super::part1("Time: 8589934592 8589934592\nDistance: 0 0").unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+    }
+}
@@ -0,0 +1,269 @@
+//! Day 7: Camel Cards, part 2. Ranking hands of cards with jokers.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{self, error_at, overflow};
+use crate::stream;
+use aoc_macros::{aoc, aoc_generator, aoc_stream};
+use eyre::{eyre, Result};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Hand([Card; 5]);
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let t1 = self.identify_type();
+        let t2 = other.identify_type();
+        t1.cmp(&t2).then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4],
+        )
+    }
+}
+
+impl std::str::FromStr for Hand {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hand = s
+            .split("")
+            .filter(|s| !s.is_empty())
+            .map(|c| {
+                c.parse::<Card>()
+                    .map_err(|_| error_at(s, c, format!("bad card {c:?}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let n = hand.len();
+        let hand = hand
+            .try_into()
+            .map_err(|_| eyre!("expected 5 cards, found {n}"))?;
+        Ok(Hand(hand))
+    }
+}
+
+impl Hand {
+    fn identify_type(&self) -> HandType {
+        let (m, j) = self
+            .0
+            .iter()
+            .fold((HashMap::new(), 0), |(mut m, j), &c| match c {
+                Card::J => (m, j + 1),
+                _ => {
+                    *m.entry(c).or_insert(0u8) += 1;
+                    (m, j)
+                }
+            });
+        let mut num = m.values().cloned().sorted().rev().collect_vec();
+        // Jokers always join the largest group.
+        match num.first_mut() {
+            Some(n) => *n += j,
+            None => num.push(j),
+        }
+        HandType::from_counts(&num)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+enum HandType {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPairs = 2,
+    ThreeOfAKind = 3,
+    FullHouse = 4,
+    FourOfAKind = 5,
+    FiveOfAKind = 6,
+}
+
+impl HandType {
+    /// Classifies a hand by its group sizes, largest first.
+    fn from_counts(num: &[u8]) -> Self {
+        match num {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPairs,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Display, FromStr)]
+#[repr(u8)]
+enum Card {
+    J = 1,
+    #[display("2")]
+    N2 = 2,
+    #[display("3")]
+    N3 = 3,
+    #[display("4")]
+    N4 = 4,
+    #[display("5")]
+    N5 = 5,
+    #[display("6")]
+    N6 = 6,
+    #[display("7")]
+    N7 = 7,
+    #[display("8")]
+    N8 = 8,
+    #[display("9")]
+    N9 = 9,
+    T = 10,
+    Q = 12,
+    K = 13,
+    A = 14,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+#[display("{hand} {bet}")]
+pub(crate) struct Entry {
+    hand: Hand,
+    bet: u32,
+}
+
+impl std::str::FromStr for Entry {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hand, bet) = s
+            .split_once(' ')
+            .ok_or_else(|| eyre!("expected `<hand> <bet>`"))?;
+        let hand = hand.parse()?;
+        let bet = bet.parse().map_err(|e| error_at(s, bet, e))?;
+        Ok(Entry { hand, bet })
+    }
+}
+
+#[aoc_generator(day7, part2)]
+pub(crate) fn gen(input: &str) -> Result<Vec<Entry>> {
+    parse::lines(input, str::parse)
+}
+
+#[aoc(day7, part2)]
+pub(crate) fn part1(input: &[Entry]) -> Result<u32> {
+    input
+        .iter()
+        .sorted_by(|a, b| a.hand.cmp(&b.hand))
+        .enumerate()
+        .try_fold(0u32, |sum, (pos, entry)| {
+            let rank = u32::try_from(pos + 1).ok()?;
+            sum.checked_add(rank.checked_mul(entry.bet)?)
+        })
+        .ok_or_else(overflow)
+}
+
+/// Winnings without holding the hands: bets are summed per distinct hand, of which there
+/// are at most 13^5, and ranks follow from the order of the hands.
+#[aoc_stream(day7, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<u32> {
+    // How often each hand was dealt, the sum of its bets, and the sum of each bet times the
+    // number of equal hands dealt before it, which rank below it as the sort is stable.
+    let mut hands: HashMap<Hand, (u128, u128, u128)> = HashMap::new();
+    stream::lines(input, |l| {
+        let entry = l.parse::<Entry>()?;
+        let (count, bets, ties) = hands.entry(entry.hand).or_default();
+        *ties += *count * u128::from(entry.bet);
+        *count += 1;
+        *bets += u128::from(entry.bet);
+        Ok(())
+    })?;
+    let mut below = 0;
+    let mut total = 0;
+    for hand in hands
+        .keys()
+        .sorted_by_cached_key(|h| (h.identify_type(), h.0))
+    {
+        let (count, bets, ties) = hands[hand];
+        total += (below + 1) * bets + ties;
+        below += count;
+    }
+    u32::try_from(total).map_err(|_| overflow())
+}
+
+/// The total winnings, with `J` a joker that counts as whatever card makes the
+/// best hand, but ranks lowest.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day7};
+///
+/// let hands = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+/// assert_eq!(day7::solve_part2(hands)?, Answer::Unsigned(5905));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&gen(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{gen, part1};
+    use eyre::Result;
+
+    const EXAMPLE: &str = include_str!("../../input/2023/day7-ex1.txt");
+    const INPUT: &str = include_str!("../../input/2023/day7.txt");
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for line in EXAMPLE.lines().chain(INPUT.lines()) {
+            crate::parse::assert_round_trip::<super::Entry>(line)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn p1() -> Result<()> {
+        let result = part1(&gen(EXAMPLE)?)?;
+        assert_eq!(result, 5905);
+        Ok(())
+    }
+
+    #[test]
+    fn stream() -> Result<()> {
+        let result = super::part2_stream(&mut EXAMPLE.as_bytes())?;
+        assert_eq!(result, part1(&gen(EXAMPLE)?)?);
+        let result = super::part2_stream(&mut INPUT.as_bytes())?;
+        assert_eq!(result, part1(&gen(INPUT)?)?);
+        // Equal hands rank in input order.
+        let input = "KK677 3\n32T3K 5\nKK677 7\n32T3K 1";
+        let result = super::part2_stream(&mut input.as_bytes())?;
+        assert_eq!(result, part1(&gen(input)?)?);
+        let e = super::part2_stream(&mut "32T3K 4294967295\nKK677 2".as_bytes());
+        assert_eq!(e.unwrap_err().to_string(), "overflow");
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() {
+        let e = gen("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2, column 4: bad card \"X\"");
+        let e = gen("32T3K 765\nT55J5J 684").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: expected 5 cards, found 6");
+        let e = gen("32T3K 7x5").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 1, column 7: invalid digit found in string"
+        );
+        let e = gen("32T3K").unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: expected `<hand> <bet>`");
+        let e = part1(&gen("32T3K 4294967295\nKK677 2").unwrap()).unwrap_err();
+        assert_eq!(e.to_string(), "overflow");
+    }
+}
@@ -0,0 +1,331 @@
+//! Day 8: Haunted Wasteland. Following left and right turns through a network.
+
+use std::collections::HashMap;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{error_at, on_line};
+use aoc_macros::{aoc, aoc_generator};
+use eyre::{bail, eyre, Result, WrapErr};
+use itertools::Itertools;
+use parse_display::{Display, FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, FromStr)]
+enum Dir {
+    L,
+    R,
+}
+
+impl Dir {
+    fn select<T: Copy>(&self, pair: &(T, T)) -> T {
+        match self {
+            Dir::L => pair.0,
+            Dir::R => pair.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Node([char; 3]);
+
+impl std::fmt::Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
+impl Node {
+    const START: Node = Node(['A', 'A', 'A']);
+    const GOAL: Node = Node(['Z', 'Z', 'Z']);
+
+    fn ends_in(&self, c: char) -> bool {
+        self.0[2] == c
+    }
+}
+
+impl std::str::FromStr for Node {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().chars().collect_vec();
+        let n = name.len();
+        let name = name
+            .try_into()
+            .map_err(|_| eyre!("expected 3 characters, found {n}"))?;
+        Ok(Node(name))
+    }
+}
+
+fn parse_node_map(s: &str) -> Result<(Node, (Node, Node))> {
+    let expected = || eyre!("expected `<node> = (<left>, <right>)`");
+    let (key, pair) = s.split_once(" = ").ok_or_else(expected)?;
+    let (left, right) = pair
+        .strip_prefix('(')
+        .and_then(|p| p.strip_suffix(')'))
+        .and_then(|p| p.split_once(", "))
+        .ok_or_else(expected)?;
+    let node = |n: &str| n.parse::<Node>().map_err(|e| error_at(s, n, e));
+    Ok((node(key)?, (node(left)?, node(right)?)))
+}
+
+type NodeMap = HashMap<Node, (Node, Node)>;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Input {
+    directions: Vec<Dir>,
+    map: NodeMap,
+    /// The nodes of `map` in input order.
+    order: Vec<Node>,
+}
+
+impl std::str::FromStr for Input {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut l = s.lines();
+        let first = l.next().ok_or_else(|| eyre!("no directions"))?;
+        let directions = first
+            .split("")
+            .filter(|s| !s.is_empty())
+            .map(|d| {
+                d.parse::<Dir>()
+                    .map_err(|_| error_at(first, d, format!("bad direction {d:?}")))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| on_line(e, 1))?;
+        if directions.is_empty() {
+            bail!("no directions");
+        }
+
+        let mut map = NodeMap::new();
+        let mut order = vec![];
+        for (i, s) in l.enumerate().filter(|(_, s)| !s.is_empty()) {
+            let (node, pair) = parse_node_map(s).map_err(|e| on_line(e, i + 2))?;
+            if map.insert(node, pair).is_some() {
+                bail!("line {}: duplicate node {node}", i + 2);
+            }
+            order.push(node);
+        }
+
+        Ok(Input {
+            directions,
+            map,
+            order,
+        })
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.directions.iter().try_for_each(|d| write!(f, "{d}"))?;
+        if !self.order.is_empty() {
+            writeln!(f)?;
+        }
+        for n in &self.order {
+            let (left, right) = self.map[n];
+            write!(f, "\n{n} = ({left}, {right})")?;
+        }
+        Ok(())
+    }
+}
+
+#[aoc_generator(day8)]
+pub(crate) fn gen(input: &str) -> Result<Input> {
+    input.parse()
+}
+
+impl Input {
+    fn next(&self, n: Node, dir: &Dir) -> Result<Node> {
+        let pair = self
+            .map
+            .get(&n)
+            .ok_or_else(|| eyre!("no map for node {n}"))?;
+        Ok(dir.select(pair))
+    }
+
+    /// After this many steps every (node, direction index) state has been seen.
+    fn max_steps(&self) -> usize {
+        self.directions.len() * (self.map.len() + 1)
+    }
+}
+
+#[aoc(day8, part1)]
+pub(crate) fn part1(input: &Input) -> Result<usize> {
+    let mut n = Node::START;
+    let mut count = 0;
+    for dir in input.directions.iter().cycle().take(input.max_steps()) {
+        count += 1;
+        n = input.next(n, dir)?;
+        if n == Node::GOAL {
+            return Ok(count);
+        }
+    }
+    bail!("{} never reaches {}", Node::START, Node::GOAL)
+}
+
+fn get_offset_and_period(node: &Node, input: &Input) -> Result<(i64, u64)> {
+    let mut n = *node;
+    let mut s = HashMap::<Node, u64>::new();
+    let mut count = 0;
+    for dir in input.directions.iter().cycle().take(2 * input.max_steps()) {
+        count += 1;
+        n = input.next(n, dir)?;
+        if n.ends_in('Z') {
+            if let Some(first) = s.insert(n, count) {
+                let period = count - first;
+                // Signed, as the first hit can come before a full period.
+                let offset = first as i64 - period as i64;
+                return Ok((offset, period));
+            }
+        }
+    }
+    bail!("{node} never cycles through a node ending in Z")
+}
+
+#[aoc(day8, part2)]
+pub(crate) fn part2(input: &Input) -> Result<u64> {
+    let start_nodes = input
+        .map
+        .keys()
+        .filter_map(|n| n.ends_in('A').then_some(*n))
+        .collect_vec();
+    let offsets_and_periods = start_nodes
+        .iter()
+        .map(|n| get_offset_and_period(n, input).wrap_err_with(|| format!("start {n}")))
+        .collect::<Result<Vec<_>>>()?;
+
+    // The input has all offsets = 0. The math is much harder otherwise.
+    if let Some((n, _)) = start_nodes
+        .iter()
+        .zip(&offsets_and_periods)
+        .find(|(_, &(first, _))| first != 0)
+    {
+        bail!("start {n}: cycle does not start at 0, which is not supported");
+    }
+
+    let periods = offsets_and_periods
+        .into_iter()
+        .map(|(_, p)| p)
+        .collect_vec();
+
+    lcmx::lcmx(&periods).ok_or_else(|| eyre!("no start nodes"))
+}
+
+/// Steps the naive solver takes at most.
+const NAIVE_STEPS: usize = 10_000_000;
+
+/// Moves every ghost in step until they all are on a node ending in Z.
+#[aoc(day8, part2, naive)]
+pub(crate) fn part2_naive(input: &Input) -> Result<usize> {
+    let mut nodes = input
+        .map
+        .keys()
+        .filter(|n| n.ends_in('A'))
+        .copied()
+        .collect_vec();
+    if nodes.is_empty() {
+        bail!("no start nodes");
+    }
+    for (count, dir) in (1..).zip(input.directions.iter().cycle().take(NAIVE_STEPS)) {
+        for n in &mut nodes {
+            *n = input.next(*n, dir)?;
+        }
+        if nodes.iter().all(|n| n.ends_in('Z')) {
+            return Ok(count);
+        }
+    }
+    bail!("no common end within {NAIVE_STEPS} steps")
+}
+
+/// The steps from `AAA` to `ZZZ`.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day8};
+///
+/// assert_eq!(day8::solve_part1("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")?, Answer::Unsigned(6));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&gen(input)?))
+}
+
+/// The steps until every ghost, starting on each node ending in `A`, is on a node ending in
+/// `Z` at once.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day8};
+///
+/// let ghosts = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+///               22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\n\
+///               XXX = (XXX, XXX)";
+/// assert_eq!(day8::solve_part2(ghosts)?, Answer::Unsigned(6));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&gen(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    use eyre::Result;
+
+    const EX1: &str = include_str!("../../input/2023/day8-ex1.txt");
+    const EX2: &str = include_str!("../../input/2023/day8-ex2.txt");
+    const EX3: &str = include_str!("../../input/2023/day8-ex3.txt");
+    const INPUT: &str = include_str!("../../input/2023/day8.txt");
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        for input in [EX1, EX2, EX3, INPUT] {
+            crate::parse::assert_round_trip::<super::Input>(input)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn p1_ex1() -> Result<()> {
+        assert_eq!(super::part1(&super::gen(EX1)?)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn p1_ex2() -> Result<()> {
+        assert_eq!(super::part1(&super::gen(EX2)?)?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn p2() -> Result<()> {
+        assert_eq!(super::part2(&super::gen(EX3)?)?, 6);
+        assert_eq!(super::part2_naive(&super::gen(EX3)?)?, 6);
+        // Cycles that don't start at 0 are fine when stepping through them.
+        assert!(super::part2(&super::gen(EX1)?).is_err());
+        assert_eq!(super::part2_naive(&super::gen(EX1)?)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() -> Result<()> {
+        let e = super::gen("LXR\n\nAAA = (BBB, CCC)").err().unwrap();
+        assert_eq!(format!("{e:#}"), "line 1, column 2: bad direction \"X\"");
+        let e = super::gen("LR\n\nAAA = (BBB, CCCC)").err().unwrap();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 3, column 13: expected 3 characters, found 4"
+        );
+        let e = super::gen("LR\n\nAAA = BBB, CCC").err().unwrap();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 3: expected `<node> = (<left>, <right>)`"
+        );
+        let e = super::gen("L\n\nAAA = (BBB, BBB)\nAAA = (CCC, CCC)")
+            .err()
+            .unwrap();
+        assert_eq!(e.to_string(), "line 4: duplicate node AAA");
+
+        let e = super::part1(&super::gen("L\n\nAAA = (BBB, BBB)")?).unwrap_err();
+        assert_eq!(e.to_string(), "no map for node BBB");
+        let e = super::part1(&super::gen("L\n\nAAA = (AAA, AAA)")?).unwrap_err();
+        assert_eq!(e.to_string(), "AAA never reaches ZZZ");
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Day 9: Mirage Maintenance. Extrapolating sequences by their differences.
+
+use std::io::BufRead;
+
+use crate::answer::{solve, Answer};
+use crate::parse::{self, error_at, overflow};
+use crate::stream;
+use aoc_macros::{aoc, aoc_generator, aoc_stream};
+use eyre::{eyre, Result, WrapErr};
+use itertools::Itertools;
+
+#[aoc_generator(day9)]
+pub(crate) fn gen(input: &str) -> Result<Vec<Vec<i32>>> {
+    parse::lines(input, parse_line)
+}
+
+fn parse_line(l: &str) -> Result<Vec<i32>> {
+    l.split_ascii_whitespace()
+        .map(|n| n.parse::<i32>().map_err(|e| error_at(l, n, e)))
+        .collect()
+}
+
+#[aoc(day9, part1)]
+pub(crate) fn part1(input: &[Vec<i32>]) -> Result<i32> {
+    let mut sum = 0i32;
+    for (i, v) in input.iter().enumerate() {
+        let next = extrapolate(v).wrap_err_with(|| format!("line {}", i + 1))?;
+        sum = sum.checked_add(next).ok_or_else(overflow)?;
+    }
+    Ok(sum)
+}
+
+#[aoc(day9, part2)]
+pub(crate) fn part2(input: &[Vec<i32>]) -> Result<i32> {
+    let mut sum = 0i32;
+    for (i, v) in input.iter().enumerate() {
+        let mut v = v.clone();
+        v.reverse();
+        let prev = extrapolate(&v).wrap_err_with(|| format!("line {}", i + 1))?;
+        sum = sum.checked_add(prev).ok_or_else(overflow)?;
+    }
+    Ok(sum)
+}
+
+#[aoc_stream(day9, part1)]
+pub(crate) fn part1_stream(input: &mut dyn BufRead) -> Result<i32> {
+    sum_extrapolated(input, false)
+}
+
+#[aoc_stream(day9, part2)]
+pub(crate) fn part2_stream(input: &mut dyn BufRead) -> Result<i32> {
+    sum_extrapolated(input, true)
+}
+
+/// The sum of each line's next value, or previous one if `backwards`.
+fn sum_extrapolated(input: &mut dyn BufRead, backwards: bool) -> Result<i32> {
+    let mut sum = 0i32;
+    stream::lines(input, |l| {
+        let mut v = parse_line(l)?;
+        if backwards {
+            v.reverse();
+        }
+        sum = sum.checked_add(extrapolate(&v)?).ok_or_else(overflow)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+fn extrapolate(x: &[i32]) -> Result<i32> {
+    let mut v = x.to_vec();
+
+    let mut sum = *v.last().ok_or_else(|| eyre!("empty sequence"))?;
+    loop {
+        v = v
+            .into_iter()
+            .tuple_windows()
+            .map(|(a, b)| b.checked_sub(a))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(overflow)?;
+        if v.len() == 1 {
+            return sum.checked_add(v[0]).ok_or_else(overflow);
+        } else if v.iter().all(|x| *x == 0) {
+            return Ok(sum);
+        }
+        sum = sum.checked_add(v[v.len() - 1]).ok_or_else(overflow)?;
+    }
+}
+
+/// The sum of the next value of each sequence.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day9};
+///
+/// let report = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+/// assert_eq!(day9::solve_part1(report)?, Answer::Unsigned(18 + 28 + 68));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part1(input: &str) -> Result<Answer> {
+    solve(input, |input| part1(&gen(input)?))
+}
+
+/// The sum of the value before the first of each sequence.
+///
+/// ```
+/// use aoc_2023::{answer::Answer, y2023::day9};
+///
+/// let report = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+/// assert_eq!(day9::solve_part2(report)?, Answer::Unsigned(2));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub fn solve_part2(input: &str) -> Result<Answer> {
+    solve(input, |input| part2(&gen(input)?))
+}
+
+#[cfg(test)]
+mod test {
+    const EX: &str = include_str!("../../input/2023/day9-ex1.txt");
+
+    #[test]
+    fn p1() -> eyre::Result<()> {
+        assert_eq!(super::part1(&super::gen(EX)?)?, 114);
+        Ok(())
+    }
+    #[test]
+    fn p2() -> eyre::Result<()> {
+        assert_eq!(super::part2(&super::gen(EX)?)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn stream() -> eyre::Result<()> {
+        let input = include_str!("../../input/2023/day9.txt");
+        assert_eq!(super::part1_stream(&mut EX.as_bytes())?, 114);
+        assert_eq!(super::part2_stream(&mut EX.as_bytes())?, 2);
+        let generated = super::gen(input)?;
+        assert_eq!(
+            super::part1_stream(&mut input.as_bytes())?,
+            super::part1(&generated)?
+        );
+        assert_eq!(
+            super::part2_stream(&mut input.as_bytes())?,
+            super::part2(&generated)?
+        );
+        let e = super::part1_stream(&mut "1 2 3\n4 x 6".as_bytes()).unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 3: invalid digit found in string"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input() -> eyre::Result<()> {
+        let e = super::gen("1 2 3\n4 x 6").unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "line 2, column 3: invalid digit found in string"
+        );
+        let e = super::part1(&super::gen("1 2 3\n\n4")?).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 2: empty sequence");
+        let e = super::part1(&super::gen("0 2147483647 -2147483648")?).unwrap_err();
+        assert_eq!(format!("{e:#}"), "line 1: overflow");
+        Ok(())
+    }
+}
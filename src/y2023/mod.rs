@@ -0,0 +1,20 @@
+//! Advent of Code 2023.
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7part1;
+pub mod day7part2;
+pub mod day8;
+pub mod day9;
+
+/// Day 7, whose parts rank hands differently and so live in [`day7part1`] and [`day7part2`].
+pub mod day7 {
+    pub use super::day7part1::solve_part1;
+    pub use super::day7part2::solve_part2;
+}
@@ -0,0 +1,14 @@
+//! Runs every solver against the known-good answers in `input/<year>/answers.txt`.
+
+use aoc_2023::answers;
+
+include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+
+fn check_line(year: u32, line: usize) -> eyre::Result<()> {
+    let all = answers::load(year)?;
+    let expected = all
+        .iter()
+        .find(|e| e.line == line)
+        .ok_or_else(|| eyre::eyre!("no answer on line {line}"))?;
+    expected.check_all()
+}
@@ -0,0 +1,45 @@
+//! The fuzz targets without libFuzzer: every cached input and mutations of it.
+//!
+//! `AOC_FUZZ_RUNS` sets the number of mutations per target and `AOC_FUZZ_SEED` varies
+//! them, e.g. `AOC_FUZZ_RUNS=100000 AOC_FUZZ_SEED=7 cargo test --test fuzz` for a longer
+//! run. Debug builds check for arithmetic overflow, release builds don't. Overflows need
+//! mutations that repeat lines of numbers, which few of the default 300 runs do, so look
+//! for them with a few thousand.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+
+use aoc_2023::fuzz::{self, Mutator};
+
+fn env(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Runs `target` on the corpus and its mutations, reporting the first input it panics on.
+fn fuzz(target: fn(&[u8])) {
+    let corpus = fuzz::corpus(Path::new("input")).unwrap();
+    assert!(!corpus.is_empty());
+    let mut mutator = Mutator::new(env("AOC_FUZZ_SEED", 2023));
+    let inputs = corpus.iter().cloned().chain(
+        (0..env("AOC_FUZZ_RUNS", 300) as usize).map(|i| mutator.mutate(&corpus[i % corpus.len()])),
+    );
+    for input in inputs {
+        // Panics are printed as they happen, so keep the input for the failure message.
+        if catch_unwind(AssertUnwindSafe(|| target(&input))).is_err() {
+            panic!("panicked on {:?}", String::from_utf8_lossy(&input));
+        }
+    }
+}
+
+#[test]
+fn solvers() {
+    fuzz(fuzz::solvers);
+}
+
+#[test]
+fn round_trips() {
+    fuzz(fuzz::round_trips);
+}
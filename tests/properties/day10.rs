@@ -0,0 +1,286 @@
+use std::collections::VecDeque;
+
+use proptest::prelude::*;
+
+/// Loops are the boundary of a shape of up to `MAX` × `MAX` cells, drawn on the corners
+/// of the cells, so maps are up to `MAX + 1` tiles square.
+const MAX: usize = 8;
+const JUNK: [u8; 7] = *b".|-LJ7F";
+
+/// `(dx, dy)` of N, E, S, W.
+const DIRS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+fn pipe(n: bool, e: bool, s: bool, w: bool) -> u8 {
+    match (n, e, s, w) {
+        (true, false, true, false) => b'|',
+        (false, true, false, true) => b'-',
+        (true, true, false, false) => b'L',
+        (true, false, false, true) => b'J',
+        (false, false, true, true) => b'7',
+        (false, true, true, false) => b'F',
+        _ => b'.',
+    }
+}
+
+/// Which neighbours a tile connects to, in the order of `DIRS`.
+fn connects(tile: u8) -> [bool; 4] {
+    match tile {
+        b'|' => [true, false, true, false],
+        b'-' => [false, true, false, true],
+        b'L' => [true, true, false, false],
+        b'J' => [true, false, false, true],
+        b'7' => [false, false, true, true],
+        b'F' => [false, true, true, false],
+        _ => [false; 4],
+    }
+}
+
+/// A set of cells whose boundary is a single simple loop: connected, without holes, and
+/// without two cells meeting only at a corner.
+struct Shape {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+}
+
+impl Shape {
+    fn get(&self, x: isize, y: isize) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && self.cells[y as usize * self.width + x as usize]
+    }
+
+    /// Grows from the first cell by each of `tries` that touches the shape and keeps it
+    /// valid.
+    fn grow(width: usize, height: usize, tries: &[(usize, usize)]) -> Self {
+        let mut shape = Shape {
+            width,
+            height,
+            cells: vec![false; width * height],
+        };
+        let (x, y) = tries[0];
+        shape.cells[(y % height) * width + x % width] = true;
+        for &(x, y) in &tries[1..] {
+            let (x, y) = ((x % width) as isize, (y % height) as isize);
+            let touches = DIRS.iter().any(|(dx, dy)| shape.get(x + dx, y + dy));
+            if shape.get(x, y) || !touches {
+                continue;
+            }
+            shape.cells[y as usize * width + x as usize] = true;
+            if !shape.is_valid() {
+                shape.cells[y as usize * width + x as usize] = false;
+            }
+        }
+        shape
+    }
+
+    fn is_valid(&self) -> bool {
+        let (w, h) = (self.width as isize, self.height as isize);
+        // Corners where the boundary would touch itself.
+        for y in -1..h {
+            for x in -1..w {
+                let square = [
+                    self.get(x, y),
+                    self.get(x + 1, y),
+                    self.get(x, y + 1),
+                    self.get(x + 1, y + 1),
+                ];
+                if square == [true, false, false, true] || square == [false, true, true, false] {
+                    return false;
+                }
+            }
+        }
+        // Holes: empty cells that can't reach the outside.
+        let mut outside = vec![false; ((w + 2) * (h + 2)) as usize];
+        let index = |x: isize, y: isize| ((y + 1) * (w + 2) + x + 1) as usize;
+        let mut queue = VecDeque::from([(-1, -1)]);
+        outside[index(-1, -1)] = true;
+        while let Some((x, y)) = queue.pop_front() {
+            for (dx, dy) in DIRS {
+                let (nx, ny) = (x + dx, y + dy);
+                if (-1..=w).contains(&nx)
+                    && (-1..=h).contains(&ny)
+                    && !outside[index(nx, ny)]
+                    && !self.get(nx, ny)
+                {
+                    outside[index(nx, ny)] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        (0..h).all(|y| (0..w).all(|x| self.get(x, y) || outside[index(x, y)]))
+    }
+
+    /// The loop around the shape as tiles on the cell corners, `.` elsewhere.
+    fn outline(&self) -> Vec<Vec<u8>> {
+        (0..=self.height as isize)
+            .map(|y| {
+                (0..=self.width as isize)
+                    .map(|x| {
+                        // Each side of the corner is an edge of the loop if it separates
+                        // a cell of the shape from one outside it.
+                        let n = self.get(x - 1, y - 1) != self.get(x, y - 1);
+                        let s = self.get(x - 1, y) != self.get(x, y);
+                        let e = self.get(x, y - 1) != self.get(x, y);
+                        let w = self.get(x - 1, y - 1) != self.get(x - 1, y);
+                        pipe(n, e, s, w)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A map with a loop, junk pipes around it and `S` somewhere on the loop.
+fn map() -> impl Strategy<Value = Vec<String>> {
+    (
+        1..=MAX,
+        1..=MAX,
+        prop::collection::vec((0..MAX, 0..MAX), 1..60),
+        prop::collection::vec(prop::sample::select(JUNK.to_vec()), (MAX + 1) * (MAX + 1)),
+        any::<prop::sample::Index>(),
+    )
+        .prop_map(|(width, height, tries, junk, start)| {
+            let mut map = Shape::grow(width, height, &tries).outline();
+            let on_loop = map
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|&(_, &t)| t != b'.')
+                        .map(move |(x, _)| (x, y))
+                })
+                .collect::<Vec<_>>();
+            let (sx, sy) = *start.get(&on_loop);
+            let width = map[0].len();
+            for (y, row) in map.iter_mut().enumerate() {
+                for (x, t) in row.iter_mut().enumerate() {
+                    if *t == b'.' {
+                        *t = junk[y * width + x];
+                    }
+                }
+            }
+            // Junk must not connect to the start, or it wouldn't be clear which way
+            // the loop goes.
+            for (i, (dx, dy)) in DIRS.iter().enumerate() {
+                let (x, y) = (sx as isize + dx, sy as isize + dy);
+                let Some(t) = map.get(y as usize).and_then(|r| r.get(x as usize)) else {
+                    continue;
+                };
+                let back = (i + 2) % 4;
+                if connects(*t)[back] && !on_loop.contains(&(x as usize, y as usize)) {
+                    map[y as usize][x as usize] = b'.';
+                }
+            }
+            map[sy][sx] = b'S';
+            map.into_iter()
+                .map(|r| String::from_utf8(r).unwrap())
+                .collect()
+        })
+}
+
+/// The tiles with `S` replaced by its pipe, and the start.
+fn parse(rows: &[String]) -> (Vec<Vec<u8>>, (usize, usize)) {
+    let mut tiles = rows
+        .iter()
+        .map(|r| r.as_bytes().to_vec())
+        .collect::<Vec<_>>();
+    let (sx, sy) = tiles
+        .iter()
+        .enumerate()
+        .find_map(|(y, r)| Some((r.iter().position(|&t| t == b'S')?, y)))
+        .unwrap();
+    let mut sides = [false; 4];
+    for (i, (dx, dy)) in DIRS.iter().enumerate() {
+        let (x, y) = (sx as isize + dx, sy as isize + dy);
+        if x >= 0 && y >= 0 {
+            if let Some(&t) = tiles.get(y as usize).and_then(|r| r.get(x as usize)) {
+                sides[i] = connects(t)[(i + 2) % 4];
+            }
+        }
+    }
+    tiles[sy][sx] = pipe(sides[0], sides[1], sides[2], sides[3]);
+    (tiles, (sx, sy))
+}
+
+/// Steps from the start to each tile of the loop, `None` off it.
+fn distances(tiles: &[Vec<u8>], (sx, sy): (usize, usize)) -> Vec<Vec<Option<usize>>> {
+    let mut dist = vec![vec![None; tiles[0].len()]; tiles.len()];
+    dist[sy][sx] = Some(0);
+    let mut queue = VecDeque::from([(sx, sy)]);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = dist[y][x].unwrap();
+        for (i, (dx, dy)) in DIRS.iter().enumerate() {
+            if !connects(tiles[y][x])[i] {
+                continue;
+            }
+            let (nx, ny) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
+            if dist[ny][nx].is_none() {
+                dist[ny][nx] = Some(d + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    dist
+}
+
+/// Tiles enclosed by the loop, found by drawing the loop three times larger and flooding
+/// the outside, which then flows between pipes that are next to each other.
+fn enclosed(tiles: &[Vec<u8>], dist: &[Vec<Option<usize>>]) -> usize {
+    let (w, h) = (tiles[0].len() * 3 + 2, tiles.len() * 3 + 2);
+    let mut wall = vec![vec![false; w]; h];
+    for (y, row) in tiles.iter().enumerate() {
+        for (x, &t) in row.iter().enumerate() {
+            if dist[y][x].is_none() {
+                continue;
+            }
+            let (cx, cy) = (x * 3 + 2, y * 3 + 2);
+            wall[cy][cx] = true;
+            for (i, (dx, dy)) in DIRS.iter().enumerate() {
+                if connects(t)[i] {
+                    wall[(cy as isize + dy) as usize][(cx as isize + dx) as usize] = true;
+                }
+            }
+        }
+    }
+    let mut outside = vec![vec![false; w]; h];
+    outside[0][0] = true;
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((x, y)) = queue.pop_front() {
+        for (dx, dy) in DIRS {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if nx < 0 || ny < 0 || nx as usize >= w || ny as usize >= h {
+                continue;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            if !wall[ny][nx] && !outside[ny][nx] {
+                outside[ny][nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    (0..tiles.len())
+        .flat_map(|y| (0..tiles[0].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| dist[y][x].is_none() && !outside[y * 3 + 2][x * 3 + 2])
+        .count()
+}
+
+proptest! {
+    #[test]
+    fn part1(rows in map()) {
+        let (tiles, start) = parse(&rows);
+        let dist = distances(&tiles, start);
+        let farthest = dist.iter().flatten().flatten().max().copied().unwrap();
+        crate::check(10, 1, &rows.join("\n"), farthest)?;
+    }
+
+    #[test]
+    fn part2(rows in map()) {
+        let (tiles, start) = parse(&rows);
+        let dist = distances(&tiles, start);
+        crate::check(10, 2, &rows.join("\n"), enclosed(&tiles, &dist))?;
+    }
+}
@@ -0,0 +1,48 @@
+use proptest::prelude::*;
+
+fn image() -> impl Strategy<Value = Vec<String>> {
+    (1..20usize, 1..20usize, 0.0..0.4f64).prop_flat_map(|(width, height, density)| {
+        let row = prop::collection::vec(prop::bool::weighted(density), width)
+            .prop_map(|r| r.iter().map(|&g| if g { '#' } else { '.' }).collect());
+        prop::collection::vec(row, height)
+    })
+}
+
+/// Sum of the distances between every pair of galaxies, with each empty row and column
+/// `expansion` times as wide.
+fn sum_distances(rows: &[String], expansion: u64) -> u64 {
+    let cells = rows.iter().map(|r| r.as_bytes()).collect::<Vec<_>>();
+    let empty_row = |y: usize| !cells[y].contains(&b'#');
+    let empty_column = |x: usize| cells.iter().all(|r| r[x] != b'#');
+    let mut galaxies = vec![];
+    for (y, row) in cells.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if c == b'#' {
+                let grow = |empty: usize| empty as u64 * (expansion - 1);
+                galaxies.push((
+                    x as u64 + grow((0..x).filter(|&x| empty_column(x)).count()),
+                    y as u64 + grow((0..y).filter(|&y| empty_row(y)).count()),
+                ));
+            }
+        }
+    }
+    let mut sum = 0;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+        }
+    }
+    sum
+}
+
+proptest! {
+    #[test]
+    fn part1(rows in image()) {
+        crate::check(11, 1, &rows.join("\n"), sum_distances(&rows, 2))?;
+    }
+
+    #[test]
+    fn part2(rows in image()) {
+        crate::check(11, 2, &rows.join("\n"), sum_distances(&rows, 1_000_000))?;
+    }
+}
@@ -0,0 +1,113 @@
+use aoc_2023::y2023::day2::{self, Bag};
+use proptest::prelude::*;
+use proptest::sample::{subsequence, Index};
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+const BAG: [u32; 3] = [12, 13, 14];
+
+/// Rounds of `(colour, count)` draws, each colour at most once per round.
+type Game = Vec<Vec<(usize, u32)>>;
+
+fn round() -> impl Strategy<Value = Vec<(usize, u32)>> {
+    (
+        subsequence(vec![0, 1, 2], 1..=3),
+        prop::array::uniform3(1..=20u32),
+    )
+        .prop_map(|(colours, counts)| {
+            colours
+                .into_iter()
+                .map(|c| (c, counts[c]))
+                .collect::<Vec<_>>()
+        })
+        .prop_shuffle()
+}
+
+fn games() -> impl Strategy<Value = Vec<Game>> {
+    prop::collection::vec(prop::collection::vec(round(), 1..6), 1..30)
+}
+
+fn input(games: &[Game]) -> String {
+    games
+        .iter()
+        .enumerate()
+        .map(|(i, rounds)| {
+            let rounds = rounds
+                .iter()
+                .map(|draws| {
+                    draws
+                        .iter()
+                        .map(|&(c, n)| format!("{n} {}", COLOURS[c]))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            format!("Game {}: {rounds}", i + 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The fewest cubes of each colour that make the game possible.
+fn minimal(rounds: &Game) -> [u32; 3] {
+    let mut bag = [0; 3];
+    for &(c, n) in rounds.iter().flatten() {
+        bag[c] = bag[c].max(n);
+    }
+    bag
+}
+
+proptest! {
+    #[test]
+    fn part1(games in games()) {
+        let expected: u32 = (1..)
+            .zip(&games)
+            .filter(|(_, g)| minimal(g).iter().zip(BAG).all(|(&n, max)| n <= max))
+            .map(|(i, _)| i)
+            .sum();
+        crate::check(2, 1, &input(&games), expected)?;
+    }
+
+    #[test]
+    fn part2(games in games()) {
+        let expected: u32 = games.iter().map(|g| minimal(g).iter().product::<u32>()).sum();
+        crate::check(2, 2, &input(&games), expected)?;
+    }
+
+    #[test]
+    fn impossible_draws(games in games()) {
+        let parsed = day2::games(&input(&games)).unwrap();
+        let bag = Bag::default();
+        for (g, rounds) in parsed.iter().zip(&games) {
+            let expected = rounds.iter().enumerate().find_map(|(i, draws)| {
+                let &(c, n) = draws.iter().find(|&&(c, n)| n > BAG[c])?;
+                Some((i, format!("{n} {}", COLOURS[c])))
+            });
+            let found = g.impossible_draw(&bag).map(|w| (w.round, w.draw.to_string()));
+            prop_assert_eq!(found, expected);
+            prop_assert_eq!(g.is_possible(&bag), g.impossible_draw(&bag).is_none());
+        }
+    }
+
+    #[test]
+    fn smallest_bag(games in games(), n in any::<Index>()) {
+        let n = 1 + n.index(games.len());
+        let needs = games.iter().map(minimal).collect::<Vec<_>>();
+        // Every bag of up to 20 cubes of each colour.
+        let mut fewest = u32::MAX;
+        for r in 0..=20 {
+            for g in 0..=20 {
+                for b in 0..=20 {
+                    let fits = needs.iter().filter(|m| m[0] <= r && m[1] <= g && m[2] <= b);
+                    if fits.count() >= n {
+                        fewest = fewest.min(r + g + b);
+                    }
+                }
+            }
+        }
+        let parsed = day2::games(&input(&games)).unwrap();
+        let bag = day2::smallest_bag(&parsed, n).unwrap();
+        prop_assert_eq!(bag.colours().map(|(_, n)| n).sum::<u32>(), fewest);
+        prop_assert!(day2::possible(&parsed, &bag).count() >= n);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeSet;
+
+use proptest::prelude::*;
+
+/// Rows of a schematic: mostly empty, with runs of digits and some symbols.
+fn schematic() -> impl Strategy<Value = Vec<String>> {
+    let cell = prop_oneof![
+        6 => Just('.'),
+        4 => prop::char::range('0', '9'),
+        1 => prop::sample::select(vec!['*', '#', '+', '$', '/', '@', '=', '%', '&', '-']),
+    ];
+    (1..16usize, 1..16usize).prop_flat_map(move |(width, height)| {
+        prop::collection::vec(
+            prop::collection::vec(cell.clone(), width).prop_map(|r| r.into_iter().collect()),
+            height,
+        )
+    })
+}
+
+/// A number and the cells it covers.
+struct Number {
+    value: u32,
+    y: usize,
+    xs: std::ops::Range<usize>,
+}
+
+/// Splits long runs of digits, which the puzzle never has, with dots.
+fn tidy(rows: Vec<String>) -> Vec<String> {
+    rows.into_iter()
+        .map(|r| {
+            let mut run = 0;
+            r.chars()
+                .map(|c| {
+                    run = if c.is_ascii_digit() { run + 1 } else { 0 };
+                    if run > 3 {
+                        run = 0;
+                        '.'
+                    } else {
+                        c
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn numbers(rows: &[Vec<char>]) -> Vec<Number> {
+    let mut numbers = vec![];
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            if x > start {
+                let value = row[start..x].iter().collect::<String>().parse().unwrap();
+                numbers.push(Number {
+                    value,
+                    y,
+                    xs: start..x,
+                });
+            } else {
+                x += 1;
+            }
+        }
+    }
+    numbers
+}
+
+/// Every cell touching the number, diagonals included.
+fn around(n: &Number) -> BTreeSet<(isize, isize)> {
+    let mut cells = BTreeSet::new();
+    for x in n.xs.clone() {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                cells.insert((x as isize + dx, n.y as isize + dy));
+            }
+        }
+    }
+    cells
+}
+
+fn at(rows: &[Vec<char>], (x, y): (isize, isize)) -> char {
+    let row = usize::try_from(y).ok().and_then(|y| rows.get(y));
+    let c = usize::try_from(x).ok().and_then(|x| row?.get(x));
+    c.copied().unwrap_or('.')
+}
+
+proptest! {
+    #[test]
+    fn part1(rows in schematic().prop_map(tidy)) {
+        let cells = rows.iter().map(|r| r.chars().collect()).collect::<Vec<Vec<_>>>();
+        let expected: u32 = numbers(&cells)
+            .iter()
+            .filter(|n| around(n).into_iter().any(|p| {
+                let c = at(&cells, p);
+                c != '.' && !c.is_ascii_digit()
+            }))
+            .map(|n| n.value)
+            .sum();
+        crate::check(3, 1, &rows.join("\n"), expected)?;
+    }
+
+    #[test]
+    fn part2(rows in schematic().prop_map(tidy)) {
+        let cells = rows.iter().map(|r| r.chars().collect()).collect::<Vec<Vec<_>>>();
+        let numbers = numbers(&cells);
+        let mut expected = 0;
+        for (y, row) in cells.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                let p = (x as isize, y as isize);
+                if c != '*' {
+                    continue;
+                }
+                let touching = numbers.iter().filter(|n| around(n).contains(&p)).collect::<Vec<_>>();
+                if let [a, b] = touching[..] {
+                    expected += a.value * b.value;
+                }
+            }
+        }
+        crate::check(3, 2, &rows.join("\n"), expected)?;
+    }
+}
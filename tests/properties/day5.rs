@@ -0,0 +1,87 @@
+use proptest::prelude::*;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// `(dest, source, count)` entries.
+type Map = Vec<(u64, u64, u64)>;
+
+/// Entries with disjoint sources, in random order, as in the puzzle.
+fn map() -> impl Strategy<Value = Map> {
+    prop::collection::vec((0..20u64, 1..30u64, 0..200u64), 0..5)
+        .prop_map(|entries| {
+            let mut source = 0;
+            entries
+                .into_iter()
+                .map(|(gap, count, dest)| {
+                    source += gap;
+                    let entry = (dest, source, count);
+                    source += count;
+                    entry
+                })
+                .collect::<Vec<_>>()
+        })
+        .prop_shuffle()
+}
+
+/// Seeds as `(start, length)` pairs, and the maps.
+fn almanac() -> impl Strategy<Value = (Vec<(u64, u64)>, Vec<Map>)> {
+    (
+        prop::collection::vec((0..150u64, 1..40u64), 1..5),
+        prop::collection::vec(map(), MAPS.len()),
+    )
+}
+
+fn input(seeds: &[(u64, u64)], maps: &[Map]) -> String {
+    let seeds = seeds
+        .iter()
+        .map(|(s, n)| format!(" {s} {n}"))
+        .collect::<String>();
+    let mut s = format!("seeds:{seeds}\n");
+    for (name, map) in MAPS.iter().zip(maps) {
+        s.push_str(&format!("\n{name} map:\n"));
+        for (dest, source, count) in map {
+            s.push_str(&format!("{dest} {source} {count}\n"));
+        }
+    }
+    s
+}
+
+fn location(maps: &[Map], seed: u64) -> u64 {
+    maps.iter().fold(seed, |x, map| {
+        map.iter()
+            .find(|&&(_, source, count)| (source..source + count).contains(&x))
+            .map_or(x, |&(dest, source, _)| x - source + dest)
+    })
+}
+
+proptest! {
+    #[test]
+    fn part1((seeds, maps) in almanac()) {
+        let expected = seeds
+            .iter()
+            .flat_map(|&(s, n)| [s, n])
+            .map(|s| location(&maps, s))
+            .min()
+            .unwrap();
+        crate::check(5, 1, &input(&seeds, &maps), expected)?;
+    }
+
+    #[test]
+    fn part2((seeds, maps) in almanac()) {
+        let expected = seeds
+            .iter()
+            .flat_map(|&(s, n)| s..s + n)
+            .map(|s| location(&maps, s))
+            .min()
+            .unwrap();
+        crate::check(5, 2, &input(&seeds, &maps), expected)?;
+    }
+}
@@ -0,0 +1,34 @@
+//! Property tests: random but valid puzzle inputs, on which every solver for a day and
+//! part, alternatives included, must agree with a brute-force reference.
+
+mod day10;
+mod day11;
+mod day2;
+mod day3;
+mod day5;
+
+use aoc_2023::answer::Answer;
+use aoc_2023::runner;
+use proptest::test_runner::TestCaseError;
+
+/// Runs every 2023 solver for `day` and `part` on `input` and compares its answer with
+/// `expected`.
+fn check(
+    day: u32,
+    part: u32,
+    input: &str,
+    expected: impl Into<Answer>,
+) -> Result<(), TestCaseError> {
+    let input = aoc_2023::inputs::normalise(input);
+    let expected = expected.into();
+    let mut any = false;
+    for solver in runner::select(Some(2023), Some(day), Some(part), None) {
+        any = true;
+        let run = solver
+            .run(&input)
+            .map_err(|e| TestCaseError::fail(format!("{solver}: {e:#}\n{input}")))?;
+        proptest::prop_assert_eq!(&run.answer, &expected, "{} on\n{}", solver, input);
+    }
+    proptest::prop_assert!(any, "no solver for day {} part {}", day, part);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Streaming solvers on inputs generated on the fly, which are never held in memory.
+
+use std::io::{BufReader, Read};
+
+use aoc_2023::answer::Answer;
+use aoc_2023::runner::{self, select};
+use eyre::Result;
+
+/// `data` over and over.
+struct Repeat {
+    data: Vec<u8>,
+    times: usize,
+    pos: usize,
+}
+
+impl Read for Repeat {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.times == 0 {
+            return Ok(0);
+        }
+        let rest = &self.data[self.pos..];
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.pos += n;
+        if self.pos == self.data.len() {
+            self.pos = 0;
+            self.times -= 1;
+        }
+        Ok(n)
+    }
+}
+
+/// Answers that are sums over lines, so repeating the input multiplies them. Day 9 part 1
+/// would overflow.
+#[test]
+fn repeated_inputs() -> Result<()> {
+    const TIMES: usize = 40;
+    for (day, part) in [(1, 1), (1, 2), (9, 2)] {
+        for solver in select(Some(2023), Some(day), Some(part), None) {
+            let path = solver.default_input(Default::default());
+            let input = runner::read_input(&path)?;
+            let Answer::Unsigned(once) = solver.run(&input)?.answer else {
+                panic!("{solver}: expected an unsigned answer");
+            };
+            let mut repeated = BufReader::new(Repeat {
+                data: format!("{input}\n").into_bytes(),
+                times: TIMES,
+                pos: 0,
+            });
+            let run = solver.run_stream(&mut repeated)?;
+            assert_eq!(run.answer, Answer::from(once * TIMES as u64), "{solver}");
+        }
+    }
+    Ok(())
+}